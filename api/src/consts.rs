@@ -43,6 +43,18 @@ pub const INTERMISSION_SLOTS: u64 = 35;
 /// The number of slots for one round.
 pub const ROUND_SLOTS: u64 = ONE_MINUTE_SLOTS;
 
+/// The minimum number of slots for one round.
+pub const MIN_ROUND_SLOTS: u64 = 25;
+
+/// The maximum number of slots for one round.
+pub const MAX_ROUND_SLOTS: u64 = ONE_HOUR_SLOTS;
+
+/// The minimum number of slots for breather between rounds.
+pub const MIN_INTERMISSION_SLOTS: u64 = 10;
+
+/// The maximum number of slots for breather between rounds.
+pub const MAX_INTERMISSION_SLOTS: u64 = 5 * ONE_MINUTE_SLOTS;
+
 /// The seed of the automation account PDA.
 pub const AUTOMATION: &[u8] = b"automation";
 
@@ -55,6 +67,9 @@ pub const STATS: &[u8] = b"stats";
 /// The seed of the config account PDA.
 pub const CONFIG: &[u8] = b"config";
 
+/// The seed of the executor account PDA.
+pub const EXECUTOR: &[u8] = b"executor";
+
 /// The seed of the miner account PDA.
 pub const MINER: &[u8] = b"miner";
 
@@ -91,15 +106,41 @@ pub const DENOMINATOR_BPS: u64 = 10_000;
 /// The address of the boost reserve token account.
 pub const BOOST_RESERVE_TOKEN: Pubkey = pubkey!("Gce36ZUsBDJsoLrfCBxUB5Sfq2DsGunofStvxFx6rBiD");
 
+/// The automation ID recorded on a miner whose round was funded by manual deploys.
+pub const MANUAL_AUTOMATION_ID: u64 = u64::MAX;
+
 /// The fee paid to bots if they checkpoint a user.
 pub const CHECKPOINT_FEE: u64 = 10_000; // 0.00001 SOL
 
+/// The maximum size of a transaction, in bytes.
+pub const MAX_TRANSACTION_SIZE: usize = 1232;
+
+/// The bytes each miner adds to a checkpoint many transaction: three account keys and their indexes.
+pub const CHECKPOINT_MANY_MINER_SIZE: usize = 3 * (32 + 1);
+
+/// The bytes of a checkpoint many transaction outside its miners: the signature, message header,
+/// blockhash, six shared accounts, instruction data, and two compute budget instructions.
+pub const CHECKPOINT_MANY_BASE_SIZE: usize = 65 + 3 + 1 + 32 + 1 + 6 * 32 + 10 + 52;
+
+/// The maximum number of miners that fit in a single checkpoint many transaction.
+pub const MAX_CHECKPOINT_MANY: usize =
+    (MAX_TRANSACTION_SIZE - CHECKPOINT_MANY_BASE_SIZE) / CHECKPOINT_MANY_MINER_SIZE;
+
 /// Amount paid to bots per transaction for auto-compounding staking yield, in lamports.
 pub const COMPOUND_FEE_PER_TRANSACTION: u64 = 7_000;
 
 /// The fee paid to the admin for each transaction.
 pub const ADMIN_FEE: u64 = 100; // 1%
 
+/// The fee paid to the protocol on losing squares, after the admin fee.
+pub const PROTOCOL_FEE: u64 = 1_000; // 10%
+
+/// The maximum admin fee rate, in basis points.
+pub const MAX_ADMIN_FEE: u64 = 500; // 5%
+
+/// The maximum protocol fee rate, in basis points.
+pub const MAX_PROTOCOL_FEE: u64 = 2_500; // 25%
+
 /// The address to receive the admin fee.
 pub const ADMIN_FEE_COLLECTOR: Pubkey = pubkey!("DyB4Kv6V613gp2LWQTq1dwDYHGKuUEoDHnCouGUtxFiX");
 
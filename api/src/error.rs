@@ -1,6 +1,6 @@
 use steel::*;
 
-#[derive(Debug, Error, Clone, Copy, PartialEq, Eq, IntoPrimitive)]
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq, IntoPrimitive, TryFromPrimitive)]
 #[repr(u32)]
 pub enum OreError {
     #[error("Amount too small")]
@@ -11,6 +11,109 @@ pub enum OreError {
 
     #[error("Invalid executor")]
     InvalidExecutor = 2,
+
+    #[error("Fee rate too high")]
+    FeeRateTooHigh = 3,
+
+    #[error("Invalid round timing")]
+    InvalidRoundTiming = 4,
+
+    #[error("Round in progress")]
+    RoundInProgress = 5,
+
+    #[error("Withdrawals are closed")]
+    WithdrawClosed = 6,
+
+    #[error("Deployment cannot be withdrawn")]
+    InvalidWithdraw = 7,
+
+    #[error("Miner account cannot be closed")]
+    InvalidCloseMiner = 8,
+
+    #[error("Miner has not checkpointed")]
+    MinerNotCheckpointed = 9,
+
+    #[error("Automation balance too low")]
+    AutomationBalanceTooLow = 10,
+
+    #[error("Executor is paused")]
+    ExecutorPaused = 11,
+
+    #[error("Insufficient treasury balance")]
+    InsufficientTreasuryBalance = 12,
+
+    #[error("No SOL balance to swap")]
+    SwapBalanceEmpty = 13,
+
+    #[error("Treasury lamports changed during swap")]
+    SwapTreasuryLamportsChanged = 14,
+
+    #[error("Mint supply changed during swap")]
+    SwapMintSupplyChanged = 15,
+
+    #[error("Swap did not use the full SOL balance")]
+    SwapIncomplete = 16,
+
+    #[error("ORE balance decreased during swap")]
+    SwapOreDecreased = 17,
+
+    #[error("Top miner verification failed")]
+    TopMinerVerificationFailed = 18,
+
+    #[error("Top miner round id mismatch")]
+    TopMinerRoundMismatch = 19,
+
+    #[error("Top miner account cannot be parsed")]
+    InvalidTopMiner = 20,
+
+    #[error("Invalid round deployed amount")]
+    InvalidRoundDeployed = 21,
+
+    #[error("Round without entropy has deployed SOL")]
+    RoundNotRefunded = 22,
+
+    #[error("Miner does not have sufficient funds for rent and rewards")]
+    InsufficientMinerBalance = 23,
+
+    #[error("Miner's round is funded by an automation")]
+    AutomationConflict = 24,
+
+    #[error("Mass round must be in the future")]
+    InvalidMassRound = 25,
+
+    #[error("Claimed ORE was not fully deposited into the stake program")]
+    StakeDepositIncomplete = 26,
+
+    #[error("Miner account cannot be migrated")]
+    InvalidMigrateMiner = 27,
 }
 
 error!(OreError);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_codes_are_stable() {
+        assert_eq!(u32::from(OreError::AmountTooSmall), 0);
+        assert_eq!(u32::from(OreError::MinerNotCheckpointed), 9);
+        assert_eq!(u32::from(OreError::TopMinerVerificationFailed), 18);
+        assert_eq!(u32::from(OreError::InsufficientMinerBalance), 23);
+        assert_eq!(u32::from(OreError::AutomationConflict), 24);
+        assert_eq!(u32::from(OreError::InvalidMassRound), 25);
+        assert_eq!(u32::from(OreError::StakeDepositIncomplete), 26);
+        assert_eq!(u32::from(OreError::InvalidMigrateMiner), 27);
+        for code in 0..=27 {
+            let error = OreError::try_from(code).unwrap();
+            assert_eq!(u32::from(error), code);
+            assert_eq!(
+                ProgramError::from(error),
+                ProgramError::Custom(code),
+                "{}",
+                error
+            );
+        }
+        assert!(OreError::try_from(28).is_err());
+    }
+}
@@ -1,12 +1,32 @@
 use serde::{Deserialize, Serialize};
 use steel::*;
 
+use crate::state::{AutomationConditions, AutomationLimits};
+
 pub enum OreEvent {
     Reset = 0,
     Bury = 1,
     Deploy = 2,
     Liq = 3,
     Claim = 4,
+    SetAdmin = 5,
+    AcceptAdmin = 6,
+    Checkpoint = 7,
+    Automation = 8,
+    Executor = 9,
+}
+
+/// The action recorded by an automation event.
+#[repr(u64)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AutomationEventType {
+    Created = 0,
+    Updated = 1,
+    Deposited = 2,
+    ClosedByUser = 3,
+    ClosedInsufficientBalance = 4,
+    Withdrawn = 5,
+    ClosedStopCondition = 6,
 }
 
 #[repr(C)]
@@ -89,7 +109,7 @@ pub struct DeployEvent {
     /// The authority of the deployer.
     pub authority: Pubkey,
 
-    /// The amount of SOL deployed per square.
+    /// The amount of SOL deployed per square (0 if amounts vary by square).
     pub amount: u64,
 
     /// The mask of the squares deployed to.
@@ -109,6 +129,12 @@ pub struct DeployEvent {
 
     /// The timestamp of the event.
     pub ts: i64,
+
+    /// The total amount of SOL deployed.
+    pub total_amount: u64,
+
+    /// The amount of SOL deployed on each square.
+    pub amounts: [u64; 25],
 }
 
 #[repr(C)]
@@ -140,15 +166,177 @@ pub struct ClaimEvent {
     /// The amount claimed.
     pub amount: u64,
 
-    /// The claim type (0 = SOL, 1 = ORE).
+    /// The claim type (0 = SOL, 1 = ORE, 2 = ORE staked, 3 = ORE buried).
     pub claim_type: u64,
 
     /// The timestamp of the event.
     pub ts: i64,
 }
 
+/// Set admin event - emitted when the admin proposes a new admin authority.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable, Serialize, Deserialize)]
+pub struct SetAdminEvent {
+    /// The event discriminator.
+    pub disc: u64,
+
+    /// The current admin authority.
+    pub authority: Pubkey,
+
+    /// The proposed admin authority (Pubkey::default() if the proposal was cancelled).
+    pub pending_admin: Pubkey,
+
+    /// The timestamp of the event.
+    pub ts: i64,
+}
+
+/// Accept admin event - emitted when the proposed admin accepts the admin authority.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable, Serialize, Deserialize)]
+pub struct AcceptAdminEvent {
+    /// The event discriminator.
+    pub disc: u64,
+
+    /// The previous admin authority.
+    pub old_authority: Pubkey,
+
+    /// The new admin authority.
+    pub new_authority: Pubkey,
+
+    /// The timestamp of the event.
+    pub ts: i64,
+}
+
+/// Checkpoint event - emitted when a miner's rewards are checkpointed.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable, Serialize, Deserialize)]
+pub struct CheckpointEvent {
+    /// The event discriminator.
+    pub disc: u64,
+
+    /// The authority of the miner.
+    pub authority: Pubkey,
+
+    /// The round that was checkpointed.
+    pub round_id: u64,
+
+    /// The amount of SOL returned to the miner.
+    pub sol_returned: u64,
+
+    /// The amount of ORE earned as the top miner.
+    pub top_miner_ore: u64,
+
+    /// The amount of ORE earned from a split top miner reward.
+    pub split_ore: u64,
+
+    /// The amount of ORE earned from the motherlode.
+    pub motherlode_ore: u64,
+
+    /// Whether the round had no entropy and all SOL was refunded (0 = false, 1 = true).
+    pub is_refund: u64,
+
+    /// Whether the round expired and rewards were forfeited (0 = false, 1 = true).
+    pub is_expired: u64,
+
+    /// The fee paid to the bot that checkpointed the miner.
+    pub bot_fee: u64,
+
+    /// Where the SOL was sent (0 = miner, 1 = authority, 2 = automation).
+    pub sol_destination: u64,
+
+    /// The timestamp of the event.
+    pub ts: i64,
+}
+
+/// Automation event - emitted when an automation is created, updated, funded, or closed.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable, Serialize, Deserialize)]
+pub struct AutomationEvent {
+    /// The event discriminator.
+    pub disc: u64,
+
+    /// The authority of the automation.
+    pub authority: Pubkey,
+
+    /// The ID of the automation.
+    pub id: u64,
+
+    /// The executor of the automation.
+    pub executor: Pubkey,
+
+    /// The event type (see `AutomationEventType`).
+    pub event_type: u64,
+
+    /// The amount of SOL deposited into, or withdrawn from, the automation by this action.
+    pub sol_amount: u64,
+
+    /// The amount of SOL to deploy on each square per round.
+    pub amount: u64,
+
+    /// The SOL balance of the automation after this action.
+    pub balance: u64,
+
+    /// The fee paid to the executor.
+    pub fee: u64,
+
+    /// The strategy of the automation.
+    pub strategy: u64,
+
+    /// The mask of squares the automation deploys to.
+    pub mask: u64,
+
+    /// Whether SOL winnings are reloaded into the automation.
+    pub reload: u64,
+
+    /// The conditions that gate whether the automation deploys.
+    pub conditions: AutomationConditions,
+
+    /// The limits that stop the automation from deploying.
+    pub limits: AutomationLimits,
+
+    /// The timestamp of the event.
+    pub ts: i64,
+}
+
+/// Executor event - emitted when an executor registers or updates its registry entry.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable, Serialize, Deserialize)]
+pub struct ExecutorEvent {
+    /// The event discriminator.
+    pub disc: u64,
+
+    /// The executor pubkey.
+    pub authority: Pubkey,
+
+    /// The event type (0 = registered, 1 = updated).
+    pub event_type: u64,
+
+    /// The hash of the executor's display name.
+    pub name_hash: [u8; 32],
+
+    /// The max flat fee (lamports) the executor charges per deploy.
+    pub max_fee: u64,
+
+    /// The max fee (bps) the executor charges per deploy for DiscretionaryBps automations.
+    pub max_bps: u64,
+
+    /// Bitmask of the automation strategies the executor supports.
+    pub strategies: u64,
+
+    /// Whether or not the executor has paused operations.
+    pub paused: u64,
+
+    /// The timestamp of the event.
+    pub ts: i64,
+}
+
 event!(ResetEvent);
 event!(BuryEvent);
 event!(DeployEvent);
 event!(LiqEvent);
 event!(ClaimEvent);
+event!(SetAdminEvent);
+event!(AcceptAdminEvent);
+event!(CheckpointEvent);
+event!(AutomationEvent);
+event!(ExecutorEvent);
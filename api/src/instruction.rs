@@ -5,19 +5,36 @@ use steel::*;
 pub enum OreInstruction {
     // Miner
     Automate = 0,
+    CheckpointMany = 1,
     Checkpoint = 2,
     ClaimSOL = 3,
     ClaimORE = 4,
     Close = 5,
     Deploy = 6,
+    Withdraw = 7,
     Log = 8,
     Reset = 9,
+    SetMinerPreferences = 10,
+    CloseMiner = 11,
+    MigrateMiner = 12,
+    ClaimAndStake = 26,
+    ClaimAndBury = 27,
+    WithdrawAutomation = 28,
+    RegisterExecutor = 29,
 
     // Admin
     Buyback = 13,
     Bury = 24,
     Wrap = 14,
     SetAdmin = 15,
+    AcceptAdmin = 16,
+    SetFeeCollector = 17,
+    SetFeeRate = 18,
+    SetAdminFee = 20,
+    SetRoundTiming = 21,
+    SetVar = 22,
+    SetWithdrawConfig = 23,
+    SetMassRound = 30,
     NewVar = 19,
     Liq = 25,
 }
@@ -26,6 +43,16 @@ pub enum OreInstruction {
 #[derive(Clone, Copy, Debug, Eq, PartialEq, TryFromPrimitive)]
 pub enum OreInstructionV2 {
     AutomateV2 = 0,
+    ClaimSOLV2 = 3,
+    ClaimOREV2 = 4,
+    DeployV2 = 6,
+    SetMinerPreferencesV2 = 10,
+}
+
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, TryFromPrimitive)]
+pub enum OreInstructionV3 {
+    AutomateV3 = 0,
 }
 
 #[repr(C)]
@@ -51,16 +78,44 @@ pub struct AutomateV2 {
     pub conditions: [u8; 24],
 }
 
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct AutomateV3 {
+    pub amount: [u8; 8],
+    pub deposit: [u8; 8],
+    pub fee: [u8; 8],
+    pub mask: [u8; 8],
+    pub strategy: u8,
+    pub reload: [u8; 8],
+    pub conditions: [u8; 24],
+    pub limits: [u8; 48],
+    pub id: [u8; 8],
+    pub require_registered_executor: u8,
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
 pub struct ClaimSOL {}
 
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct ClaimSOLV2 {
+    pub recipient: [u8; 32],
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
 pub struct ClaimORE {
     pub bps: [u8; 8],
 }
 
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct ClaimOREV2 {
+    pub bps: [u8; 8],
+    pub recipient: [u8; 32],
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
 pub struct Deploy {
@@ -68,6 +123,37 @@ pub struct Deploy {
     pub squares: [u8; 4],
 }
 
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct DeployV2 {
+    pub amounts: [[u8; 8]; 25],
+    pub top_up: u8,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct Withdraw {
+    pub square: [u8; 8],
+    pub amount: [u8; 8],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct RegisterExecutor {
+    pub name_hash: [u8; 32],
+    pub max_fee: [u8; 8],
+    pub max_bps: [u8; 8],
+    pub strategies: u8,
+    pub paused: u8,
+    pub uri: [u8; 64],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct WithdrawAutomation {
+    pub amount: [u8; 8],
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
 pub struct Log {}
@@ -82,6 +168,10 @@ pub struct SetAdmin {
     pub admin: [u8; 32],
 }
 
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct AcceptAdmin {}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
 pub struct SetFeeCollector {
@@ -114,10 +204,36 @@ pub struct Bury {
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
 pub struct Checkpoint {}
 
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct CheckpointMany {}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct ClaimAndStake {
+    pub bps: [u8; 8],
+    pub compound_fee: [u8; 8],
+    pub compound_fee_deposit: [u8; 8],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct ClaimAndBury {
+    pub bps: [u8; 8],
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
 pub struct Close {}
 
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct CloseMiner {}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct MigrateMiner {}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
 pub struct NewVar {
@@ -132,6 +248,44 @@ pub struct SetAdminFee {
     pub admin_fee: [u8; 8],
 }
 
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct SetMinerPreferences {
+    pub auto_return: u8,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct SetMinerPreferencesV2 {
+    pub auto_return: u8,
+    pub claim_delegate: [u8; 32],
+    pub claim_destination: [u8; 32],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct SetRoundTiming {
+    pub round_slots: [u8; 8],
+    pub intermission_slots: [u8; 8],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct SetVar {}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct SetWithdrawConfig {
+    pub cutoff_slots: [u8; 8],
+    pub penalty_rate: [u8; 8],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct SetMassRound {
+    pub mass_round_id: [u8; 8],
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
 pub struct SetSwapProgram {}
@@ -142,15 +296,38 @@ pub struct SetVarAddress {}
 
 instruction!(OreInstruction, Automate);
 instruction!(OreInstruction, Close);
+instruction!(OreInstruction, CloseMiner);
 instruction!(OreInstruction, Checkpoint);
+instruction!(OreInstruction, CheckpointMany);
 instruction!(OreInstruction, ClaimSOL);
 instruction!(OreInstruction, ClaimORE);
+instruction!(OreInstruction, ClaimAndStake);
+instruction!(OreInstruction, ClaimAndBury);
 instruction!(OreInstruction, Deploy);
+instruction!(OreInstruction, Withdraw);
+instruction!(OreInstruction, WithdrawAutomation);
 instruction!(OreInstruction, Log);
+instruction!(OreInstruction, MigrateMiner);
 instruction!(OreInstruction, Wrap);
 instruction!(OreInstruction, Buyback);
 instruction!(OreInstruction, Bury);
+instruction!(OreInstruction, RegisterExecutor);
 instruction!(OreInstruction, Reset);
+instruction!(OreInstruction, SetMinerPreferences);
 instruction!(OreInstruction, SetAdmin);
+instruction!(OreInstruction, AcceptAdmin);
+instruction!(OreInstruction, SetFeeCollector);
+instruction!(OreInstruction, SetFeeRate);
+instruction!(OreInstruction, SetAdminFee);
+instruction!(OreInstruction, SetRoundTiming);
+instruction!(OreInstruction, SetVar);
+instruction!(OreInstruction, SetWithdrawConfig);
+instruction!(OreInstruction, SetMassRound);
 instruction!(OreInstruction, NewVar);
 instruction!(OreInstructionV2, AutomateV2);
+instruction!(OreInstructionV2, ClaimSOLV2);
+instruction!(OreInstructionV2, ClaimOREV2);
+instruction!(OreInstructionV2, DeployV2);
+instruction!(OreInstructionV2, SetMinerPreferencesV2);
+
+instruction!(OreInstructionV3, AutomateV3);
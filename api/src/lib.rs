@@ -2,16 +2,20 @@ pub mod consts;
 pub mod error;
 pub mod event;
 pub mod instruction;
+pub mod rewards;
 pub mod sdk;
 pub mod state;
+pub mod strategy;
 
 pub mod prelude {
     pub use crate::consts::*;
     pub use crate::error::*;
     pub use crate::event::*;
     pub use crate::instruction::*;
+    pub use crate::rewards::*;
     pub use crate::sdk::*;
     pub use crate::state::*;
+    pub use crate::strategy::*;
 }
 
 use steel::*;
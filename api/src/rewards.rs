@@ -0,0 +1,184 @@
+use crate::{
+    consts::SPLIT_ADDRESS,
+    state::{Miner, Round},
+};
+
+/// A breakdown of the rewards a miner earns in a round.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RewardBreakdown {
+    /// The amount of SOL returned to the miner on each square.
+    pub sol: [u64; 25],
+
+    /// The miner's share of the admin fee.
+    pub admin_fee: u64,
+
+    /// The miner's share of the protocol fee.
+    pub protocol_fee: u64,
+
+    /// The amount of ORE earned as the top miner.
+    pub top_miner_ore: u64,
+
+    /// The amount of ORE earned from a split top miner reward.
+    pub split_ore: u64,
+
+    /// The amount of ORE earned from the motherlode.
+    pub motherlode_ore: u64,
+
+    /// Whether the round had no entropy and all SOL was refunded.
+    pub is_refund: bool,
+}
+
+impl RewardBreakdown {
+    /// Returns the total SOL returned to the miner.
+    pub fn total_sol(&self) -> u64 {
+        self.sol.iter().sum()
+    }
+
+    /// Returns the total ORE earned by the miner.
+    pub fn total_ore(&self) -> u64 {
+        self.top_miner_ore + self.split_ore + self.motherlode_ore
+    }
+}
+
+/// Calculates the rewards a miner earns in a round, given the round's rng.
+/// If the rng is None, the round had no entropy and the miner is refunded all SOL deployed.
+pub fn calculate_rewards(round: &Round, miner: &Miner, rng: Option<u64>) -> RewardBreakdown {
+    let mut rewards = RewardBreakdown::default();
+
+    // Refund all SOL if the round has no entropy.
+    let Some(r) = rng else {
+        rewards.sol = miner.deployed;
+        rewards.is_refund = true;
+        return rewards;
+    };
+
+    let winning_square = round.winning_square(r);
+    for i in 0..25 {
+        // Skip squares the miner did not deploy to.
+        let deployed = miner.deployed[i];
+        let sq_total = round.deployed[i];
+        if deployed == 0 || sq_total == 0 {
+            continue;
+        }
+
+        // Return a proportional share of the square after fees.
+        let (sq_admin, sq_protocol) = round.square_fees(i, winning_square);
+        let sq_returned = sq_total.saturating_sub(sq_admin + sq_protocol);
+        rewards.sol[i] = proportion(sq_returned, deployed, sq_total);
+        rewards.admin_fee += proportion(sq_admin, deployed, sq_total);
+        rewards.protocol_fee += proportion(sq_protocol, deployed, sq_total);
+    }
+
+    // ORE is only earned on the winning square.
+    // ORE is distributed by SOL deployed, or by mass if the round is mass weighted.
+    let miner_weight = miner.weights(round)[winning_square];
+    let round_weight = round.weights()[winning_square];
+    if miner.deployed[winning_square] == 0 || round_weight == 0 {
+        return rewards;
+    }
+
+    // Split the top miner reward by weight, or pay it all to the top miner.
+    if round.top_miner == SPLIT_ADDRESS {
+        rewards.split_ore = proportion(round.top_miner_reward(), miner_weight, round_weight);
+    } else {
+        let top_miner_sample = round.top_miner_sample(r, winning_square);
+        if miner.is_top_miner(round, top_miner_sample, winning_square) {
+            rewards.top_miner_ore = round.top_miner_reward();
+        }
+    }
+
+    // Split the motherlode by weight.
+    rewards.motherlode_ore = proportion(round.motherlode, miner_weight, round_weight);
+
+    rewards
+}
+
+fn proportion(amount: u64, numerator: u64, denominator: u64) -> u64 {
+    (amount as u128 * numerator as u128 / denominator as u128) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use steel::Zeroable;
+
+    use super::*;
+    use crate::consts::{ADMIN_FEE, PROTOCOL_FEE};
+
+    fn round(rng: u64) -> (Round, usize) {
+        let mut round = Round {
+            id: 1,
+            admin_fee_rate: ADMIN_FEE,
+            protocol_fee_rate: PROTOCOL_FEE,
+            ..Round::zeroed()
+        };
+        round.rewards[0] = 1_000;
+        round.deployed = [1_000; 25];
+        let winning_square = round.winning_square(rng);
+        (round, winning_square)
+    }
+
+    #[test]
+    fn test_refund_without_rng() {
+        let (round, _) = round(7);
+        let mut miner = Miner::zeroed();
+        miner.deployed[3] = 400;
+        miner.deployed[4] = 100;
+        let rewards = calculate_rewards(&round, &miner, None);
+        assert!(rewards.is_refund);
+        assert_eq!(rewards.total_sol(), 500);
+        assert_eq!(rewards.total_ore(), 0);
+    }
+
+    #[test]
+    fn test_sol_matches_checkpoint_formula() {
+        let rng = 7;
+        let (round, winning_square) = round(rng);
+        let losing_square = (winning_square + 1) % 25;
+        let mut miner = Miner::zeroed();
+        miner.deployed[winning_square] = 500;
+        miner.deployed[losing_square] = 500;
+        let rewards = calculate_rewards(&round, &miner, Some(rng));
+
+        // Winning square: 1% admin fee on 1,000 SOL, half returned.
+        assert_eq!(rewards.sol[winning_square], 495);
+
+        // Losing square: 1% admin fee, then 10% protocol fee on the remainder, half returned.
+        assert_eq!(rewards.sol[losing_square], 445);
+        assert_eq!(rewards.admin_fee, 10);
+        assert_eq!(rewards.protocol_fee, 49);
+    }
+
+    #[test]
+    fn test_split_and_motherlode_by_weight() {
+        let rng = 7;
+        let (mut round, winning_square) = round(rng);
+        round.top_miner = SPLIT_ADDRESS;
+        round.motherlode = 2_000;
+        let mut miner = Miner::zeroed();
+        miner.deployed[winning_square] = 250;
+        let rewards = calculate_rewards(&round, &miner, Some(rng));
+        assert_eq!(rewards.split_ore, 250);
+        assert_eq!(rewards.top_miner_ore, 0);
+        assert_eq!(rewards.motherlode_ore, 500);
+    }
+
+    #[test]
+    fn test_top_miner_takes_full_reward() {
+        let rng = 7;
+        let (round, winning_square) = round(rng);
+        let sample = round.top_miner_sample(rng, winning_square);
+
+        // The miner holding the sampled range earns the full reward.
+        let mut miner = Miner::zeroed();
+        miner.cumulative[winning_square] = sample;
+        miner.deployed[winning_square] = 1;
+        let rewards = calculate_rewards(&round, &miner, Some(rng));
+        assert_eq!(rewards.top_miner_ore, 1_000);
+        assert_eq!(rewards.split_ore, 0);
+
+        // Any other miner on the square earns nothing.
+        miner.cumulative[winning_square] = sample + 1;
+        let rewards = calculate_rewards(&round, &miner, Some(rng));
+        assert_eq!(rewards.top_miner_ore, 0);
+    }
+}
@@ -3,7 +3,7 @@ use spl_associated_token_account::get_associated_token_address;
 use steel::*;
 
 use crate::{
-    consts::{BOARD, MINT_ADDRESS, SOL_MINT},
+    consts::{BOARD, MAX_CHECKPOINT_MANY, MINT_ADDRESS, SOL_MINT},
     instruction::*,
     state::*,
 };
@@ -22,7 +22,7 @@ pub fn program_log(accounts: &[AccountInfo], msg: &[u8]) -> Result<(), ProgramEr
     invoke_signed(&log(*accounts[0].key, msg), accounts, &crate::ID, &[BOARD])
 }
 
-// let [signer_info, automation_info, executor_info, miner_info, system_program] = accounts else {
+// let [signer_info, automation_info, executor_info, miner_info, system_program, board_info, ore_program, executor_registry_info] =
 
 pub fn automate(
     signer: Pubkey,
@@ -35,17 +35,9 @@ pub fn automate(
     reload: bool,
     conditions: AutomationConditions,
 ) -> Instruction {
-    let automation_address = automation_pda(signer).0;
-    let miner_address = miner_pda(signer).0;
     Instruction {
         program_id: crate::ID,
-        accounts: vec![
-            AccountMeta::new(signer, true),
-            AccountMeta::new(automation_address, false),
-            AccountMeta::new(executor, false),
-            AccountMeta::new(miner_address, false),
-            AccountMeta::new_readonly(system_program::ID, false),
-        ],
+        accounts: automate_accounts(signer, 0, executor),
         data: AutomateV2 {
             amount: amount.to_le_bytes(),
             deposit: deposit.to_le_bytes(),
@@ -59,6 +51,54 @@ pub fn automate(
     }
 }
 
+#[derive(Clone, Copy, Debug)]
+pub struct AutomateArgs {
+    pub id: u64,
+    pub amount: u64,
+    pub deposit: u64,
+    pub executor: Pubkey,
+    pub fee: u64,
+    pub mask: u64,
+    pub strategy: AutomationStrategy,
+    pub reload: bool,
+    pub conditions: AutomationConditions,
+    pub limits: AutomationLimits,
+    pub require_registered_executor: bool,
+}
+
+pub fn automate_v3(signer: Pubkey, args: AutomateArgs) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: automate_accounts(signer, args.id, args.executor),
+        data: AutomateV3 {
+            amount: args.amount.to_le_bytes(),
+            deposit: args.deposit.to_le_bytes(),
+            fee: args.fee.to_le_bytes(),
+            mask: args.mask.to_le_bytes(),
+            strategy: args.strategy as u8,
+            reload: (args.reload as u64).to_le_bytes(),
+            conditions: args.conditions.to_bytes(),
+            limits: args.limits.to_bytes(),
+            id: args.id.to_le_bytes(),
+            require_registered_executor: args.require_registered_executor as u8,
+        }
+        .to_bytes(),
+    }
+}
+
+fn automate_accounts(signer: Pubkey, id: u64, executor: Pubkey) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new(automation_pda_with_id(signer, id).0, false),
+        AccountMeta::new(executor, false),
+        AccountMeta::new(miner_pda(signer).0, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new(board_pda().0, false),
+        AccountMeta::new_readonly(crate::ID, false),
+        AccountMeta::new_readonly(executor_pda(executor).0, false),
+    ]
+}
+
 pub fn claim_sol(signer: Pubkey) -> Instruction {
     let board_address = board_pda().0;
     let miner_address = miner_pda(signer).0;
@@ -75,6 +115,29 @@ pub fn claim_sol(signer: Pubkey) -> Instruction {
     }
 }
 
+// let [signer_info, authority_info, board_info, miner_info, recipient_info, system_program, ore_program] =
+
+pub fn claim_sol_v2(signer: Pubkey, authority: Pubkey, recipient: Pubkey) -> Instruction {
+    let board_address = board_pda().0;
+    let miner_address = miner_pda(authority).0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(authority, false),
+            AccountMeta::new(board_address, false),
+            AccountMeta::new(miner_address, false),
+            AccountMeta::new(recipient, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(crate::ID, false),
+        ],
+        data: ClaimSOLV2 {
+            recipient: recipient.to_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
 // let [signer_info, board_info, miner_info, mint_info, recipient_info, treasury_info, treasury_tokens_info, system_program, token_program, associated_token_program, ore_program] =
 
 pub fn claim_ore(signer: Pubkey, bps: u64) -> Instruction {
@@ -105,6 +168,124 @@ pub fn claim_ore(signer: Pubkey, bps: u64) -> Instruction {
     }
 }
 
+// let [signer_info, board_info, miner_info, mint_info, sender_info, treasury_info, treasury_tokens_info, stake_info, stake_tokens_info, stake_treasury_info, stake_vesting_info, system_program, token_program, associated_token_program, ore_program, ore_stake_program] =
+
+pub fn claim_and_stake(
+    signer: Pubkey,
+    bps: u64,
+    compound_fee: u64,
+    compound_fee_deposit: u64,
+) -> Instruction {
+    let board_address = board_pda().0;
+    let miner_address = miner_pda(signer).0;
+    let sender_address = get_associated_token_address(&signer, &MINT_ADDRESS);
+    let treasury_address = treasury_pda().0;
+    let treasury_tokens_address = get_associated_token_address(&treasury_address, &MINT_ADDRESS);
+    let stake_address = ore_stake_api::state::stake_pda(signer).0;
+    let stake_tokens_address = get_associated_token_address(&stake_address, &MINT_ADDRESS);
+    let stake_treasury_address = ore_stake_api::state::treasury_pda().0;
+    let stake_vesting_address = ore_stake_api::state::vesting_pda().0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(board_address, false),
+            AccountMeta::new(miner_address, false),
+            AccountMeta::new(MINT_ADDRESS, false),
+            AccountMeta::new(sender_address, false),
+            AccountMeta::new(treasury_address, false),
+            AccountMeta::new(treasury_tokens_address, false),
+            AccountMeta::new(stake_address, false),
+            AccountMeta::new(stake_tokens_address, false),
+            AccountMeta::new(stake_treasury_address, false),
+            AccountMeta::new(stake_vesting_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+            AccountMeta::new_readonly(crate::ID, false),
+            AccountMeta::new_readonly(ore_stake_api::ID, false),
+        ],
+        data: ClaimAndStake {
+            bps: bps.to_le_bytes(),
+            compound_fee: compound_fee.to_le_bytes(),
+            compound_fee_deposit: compound_fee_deposit.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+// let [signer_info, board_info, miner_info, mint_info, treasury_info, treasury_ore_info, stake_treasury_info, stake_treasury_tokens_info, stake_vesting_info, token_program, ore_program, ore_stake_program] =
+
+pub fn claim_and_bury(signer: Pubkey, bps: u64) -> Instruction {
+    let board_address = board_pda().0;
+    let miner_address = miner_pda(signer).0;
+    let treasury_address = treasury_pda().0;
+    let treasury_ore_address = get_associated_token_address(&treasury_address, &MINT_ADDRESS);
+    let stake_treasury_address = ore_stake_api::state::treasury_pda().0;
+    let stake_treasury_ore_address =
+        get_associated_token_address(&stake_treasury_address, &MINT_ADDRESS);
+    let stake_vesting_address = ore_stake_api::state::vesting_pda().0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(board_address, false),
+            AccountMeta::new(miner_address, false),
+            AccountMeta::new(MINT_ADDRESS, false),
+            AccountMeta::new(treasury_address, false),
+            AccountMeta::new(treasury_ore_address, false),
+            AccountMeta::new(stake_treasury_address, false),
+            AccountMeta::new(stake_treasury_ore_address, false),
+            AccountMeta::new(stake_vesting_address, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(crate::ID, false),
+            AccountMeta::new_readonly(ore_stake_api::ID, false),
+        ],
+        data: ClaimAndBury {
+            bps: bps.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+// let [signer_info, authority_info, board_info, miner_info, mint_info, recipient_info, recipient_tokens_info, treasury_info, treasury_tokens_info, system_program, token_program, associated_token_program, ore_program] =
+
+pub fn claim_ore_v2(
+    signer: Pubkey,
+    authority: Pubkey,
+    recipient: Pubkey,
+    recipient_tokens: Pubkey,
+    bps: u64,
+) -> Instruction {
+    let board_address = board_pda().0;
+    let miner_address = miner_pda(authority).0;
+    let treasury_address = treasury_pda().0;
+    let treasury_tokens_address = get_associated_token_address(&treasury_address, &MINT_ADDRESS);
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(authority, false),
+            AccountMeta::new(board_address, false),
+            AccountMeta::new(miner_address, false),
+            AccountMeta::new(MINT_ADDRESS, false),
+            AccountMeta::new_readonly(recipient, false),
+            AccountMeta::new(recipient_tokens, false),
+            AccountMeta::new(treasury_address, false),
+            AccountMeta::new(treasury_tokens_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+            AccountMeta::new_readonly(crate::ID, false),
+        ],
+        data: ClaimOREV2 {
+            bps: bps.to_le_bytes(),
+            recipient: recipient.to_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
 // let [signer_info, authority_info, automation_info, board_info, miner_info, round_info, system_program] =
 
 pub fn deploy(
@@ -113,6 +294,8 @@ pub fn deploy(
     amount: u64,
     round_id: u64,
     squares: [bool; 25],
+    entropy_var: Pubkey,
+    entropy_program: Pubkey,
 ) -> Instruction {
     let automation_address = automation_pda(authority).0;
     let board_address = board_pda().0;
@@ -120,7 +303,6 @@ pub fn deploy(
     let miner_address = miner_pda(authority).0;
     let round_address = round_pda(round_id).0;
     let treasury_address = treasury_pda().0;
-    let entropy_var_address = entropy_api::state::var_pda(board_address, 0).0;
 
     // Convert array of 25 booleans into a 32-bit mask where each bit represents whether
     // that square index is selected (1) or not (0)
@@ -145,8 +327,8 @@ pub fn deploy(
             AccountMeta::new_readonly(system_program::ID, false),
             AccountMeta::new_readonly(crate::ID, false),
             // Entropy accounts.
-            AccountMeta::new(entropy_var_address, false),
-            AccountMeta::new_readonly(entropy_api::ID, false),
+            AccountMeta::new(entropy_var, false),
+            AccountMeta::new_readonly(entropy_program, false),
         ],
         data: Deploy {
             amount: amount.to_le_bytes(),
@@ -156,6 +338,189 @@ pub fn deploy(
     }
 }
 
+// let [signer_info, authority_info, automation_info, board_info, config_info, miner_info, round_info, treasury_info, system_program, ore_program] =
+// let (executor_registry_info, entropy_accounts) = ...
+
+#[allow(clippy::too_many_arguments)]
+pub fn deploy_v2(
+    signer: Pubkey,
+    authority: Pubkey,
+    automation_id: u64,
+    round_id: u64,
+    amounts: [u64; 25],
+    top_up: bool,
+    entropy_var: Pubkey,
+    entropy_program: Pubkey,
+) -> Instruction {
+    let automation_address = automation_pda_with_id(authority, automation_id).0;
+    let board_address = board_pda().0;
+    let config_address = config_pda().0;
+    let miner_address = miner_pda(authority).0;
+    let round_address = round_pda(round_id).0;
+    let treasury_address = treasury_pda().0;
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new(authority, false),
+        AccountMeta::new(automation_address, false),
+        AccountMeta::new(board_address, false),
+        AccountMeta::new(config_address, false),
+        AccountMeta::new(miner_address, false),
+        AccountMeta::new(round_address, false),
+        AccountMeta::new(treasury_address, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new_readonly(crate::ID, false),
+    ];
+
+    // Executors deploying for an authority pass their registry entry.
+    if signer != authority {
+        accounts.push(AccountMeta::new_readonly(executor_pda(signer).0, false));
+    }
+
+    // Entropy accounts.
+    accounts.push(AccountMeta::new(entropy_var, false));
+    accounts.push(AccountMeta::new_readonly(entropy_program, false));
+    Instruction {
+        program_id: crate::ID,
+        accounts,
+        data: DeployV2 {
+            amounts: amounts.map(u64::to_le_bytes),
+            top_up: top_up as u8,
+        }
+        .to_bytes(),
+    }
+}
+
+// let [signer_info, automation_info, board_info, config_info, miner_info, round_info, treasury_info, system_program] =
+
+pub fn withdraw(
+    signer: Pubkey,
+    automation_id: u64,
+    round_id: u64,
+    square: u64,
+    amount: u64,
+) -> Instruction {
+    let automation_address = automation_pda_with_id(signer, automation_id).0;
+    let board_address = board_pda().0;
+    let config_address = config_pda().0;
+    let miner_address = miner_pda(signer).0;
+    let round_address = round_pda(round_id).0;
+    let treasury_address = treasury_pda().0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(automation_address, false),
+            AccountMeta::new_readonly(board_address, false),
+            AccountMeta::new(config_address, false),
+            AccountMeta::new(miner_address, false),
+            AccountMeta::new(round_address, false),
+            AccountMeta::new(treasury_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: Withdraw {
+            square: square.to_le_bytes(),
+            amount: amount.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+// let [signer_info, miner_info, system_program] = accounts else {
+
+pub fn set_miner_preferences(signer: Pubkey, auto_return: bool) -> Instruction {
+    let miner_address = miner_pda(signer).0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(miner_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: SetMinerPreferences {
+            auto_return: auto_return as u8,
+        }
+        .to_bytes(),
+    }
+}
+
+pub fn set_miner_preferences_v2(
+    signer: Pubkey,
+    auto_return: bool,
+    claim_delegate: Pubkey,
+    claim_destination: Pubkey,
+) -> Instruction {
+    let miner_address = miner_pda(signer).0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(miner_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: SetMinerPreferencesV2 {
+            auto_return: auto_return as u8,
+            claim_delegate: claim_delegate.to_bytes(),
+            claim_destination: claim_destination.to_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+// let [signer_info, automation_info, board_info, system_program, ore_program] = accounts else {
+
+pub fn withdraw_automation(signer: Pubkey, id: u64, amount: u64) -> Instruction {
+    let automation_address = automation_pda_with_id(signer, id).0;
+    let board_address = board_pda().0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(automation_address, false),
+            AccountMeta::new(board_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(crate::ID, false),
+        ],
+        data: WithdrawAutomation {
+            amount: amount.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+// let [signer_info, executor_info, board_info, system_program, ore_program] = accounts else {
+
+pub fn register_executor(
+    signer: Pubkey,
+    name_hash: [u8; 32],
+    max_fee: u64,
+    max_bps: u64,
+    strategies: u8,
+    paused: bool,
+    uri: [u8; 64],
+) -> Instruction {
+    let executor_address = executor_pda(signer).0;
+    let board_address = board_pda().0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(executor_address, false),
+            AccountMeta::new(board_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(crate::ID, false),
+        ],
+        data: RegisterExecutor {
+            name_hash,
+            max_fee: max_fee.to_le_bytes(),
+            max_bps: max_bps.to_le_bytes(),
+            strategies,
+            paused: paused as u8,
+            uri,
+        }
+        .to_bytes(),
+    }
+}
+
 // let [pool, user_source_token, user_destination_token, a_vault, b_vault, a_token_vault, b_token_vault, a_vault_lp_mint, b_vault_lp_mint, a_vault_lp, b_vault_lp, protocol_token_fee, user_key, vault_program, token_program] =
 
 pub fn buyback(
@@ -270,6 +635,8 @@ pub fn reset(
     fee_collector: Pubkey,
     round_id: u64,
     top_miner: Pubkey,
+    entropy_var: Pubkey,
+    entropy_program: Pubkey,
 ) -> Instruction {
     let board_address = board_pda().0;
     let config_address = config_pda().0;
@@ -279,7 +646,6 @@ pub fn reset(
     let top_miner_address = miner_pda(top_miner).0;
     let treasury_address = treasury_pda().0;
     let treasury_tokens_address = treasury_tokens_address();
-    let entropy_var_address = entropy_api::state::var_pda(board_address, 0).0;
     let mint_authority_address = ore_mint_api::state::authority_pda().0;
     Instruction {
         program_id: crate::ID,
@@ -299,8 +665,8 @@ pub fn reset(
             AccountMeta::new_readonly(crate::ID, false),
             AccountMeta::new_readonly(sysvar::slot_hashes::ID, false),
             // Entropy accounts.
-            AccountMeta::new(entropy_var_address, false),
-            AccountMeta::new_readonly(entropy_api::ID, false),
+            AccountMeta::new(entropy_var, false),
+            AccountMeta::new_readonly(entropy_program, false),
             // Mint accounts.
             AccountMeta::new(mint_authority_address, false),
             AccountMeta::new_readonly(ore_mint_api::ID, false),
@@ -329,10 +695,56 @@ pub fn close(signer: Pubkey, round_id: u64, rent_payer: Pubkey) -> Instruction {
     }
 }
 
-// let [signer_info, automation_info, board_info, miner_info, round_info, treasury_info, system_program] =
+// let [signer_info, automation_info, miner_info, treasury_info, system_program] = accounts else {
 
-pub fn checkpoint(signer: Pubkey, authority: Pubkey, round_id: u64) -> Instruction {
-    let automation_address = automation_pda(authority).0;
+pub fn close_miner(signer: Pubkey) -> Instruction {
+    let automation_address = automation_pda(signer).0;
+    let miner_address = miner_pda(signer).0;
+    let treasury_address = treasury_pda().0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(automation_address, false),
+            AccountMeta::new(miner_address, false),
+            AccountMeta::new(treasury_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: CloseMiner {}.to_bytes(),
+    }
+}
+
+// let [signer_info, new_authority_info, automation_info, miner_info, new_miner_info, treasury_info, system_program] =
+
+pub fn migrate_miner(signer: Pubkey, new_authority: Pubkey) -> Instruction {
+    let automation_address = automation_pda(signer).0;
+    let miner_address = miner_pda(signer).0;
+    let new_miner_address = miner_pda(new_authority).0;
+    let treasury_address = treasury_pda().0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(new_authority, true),
+            AccountMeta::new_readonly(automation_address, false),
+            AccountMeta::new(miner_address, false),
+            AccountMeta::new(new_miner_address, false),
+            AccountMeta::new_readonly(treasury_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: MigrateMiner {}.to_bytes(),
+    }
+}
+
+// let [signer_info, authority_info, automation_info, board_info, miner_info, round_info, treasury_info, system_program, ore_program] =
+
+pub fn checkpoint(
+    signer: Pubkey,
+    authority: Pubkey,
+    automation_id: u64,
+    round_id: u64,
+) -> Instruction {
+    let automation_address = automation_pda_with_id(authority, automation_id).0;
     let miner_address = miner_pda(authority).0;
     let board_address = board_pda().0;
     let round_address = round_pda(round_id).0;
@@ -348,19 +760,62 @@ pub fn checkpoint(signer: Pubkey, authority: Pubkey, round_id: u64) -> Instructi
             AccountMeta::new(round_address, false),
             AccountMeta::new(treasury_address, false),
             AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(crate::ID, false),
         ],
         data: Checkpoint {}.to_bytes(),
     }
 }
 
+// let [signer_info, board_info, round_info, treasury_info, system_program, ore_program] = accounts else {
+// for [authority_info, miner_info, automation_info] in miner_accounts.chunks(3) {
+
+pub fn checkpoint_many(
+    signer: Pubkey,
+    round_id: u64,
+    miners: &[(Pubkey, u64)],
+) -> Vec<Instruction> {
+    let board_address = board_pda().0;
+    let round_address = round_pda(round_id).0;
+    let treasury_address = treasury_pda().0;
+    miners
+        .chunks(MAX_CHECKPOINT_MANY)
+        .map(|chunk| {
+            let mut accounts = vec![
+                AccountMeta::new(signer, true),
+                AccountMeta::new(board_address, false),
+                AccountMeta::new(round_address, false),
+                AccountMeta::new(treasury_address, false),
+                AccountMeta::new_readonly(system_program::ID, false),
+                AccountMeta::new_readonly(crate::ID, false),
+            ];
+            for (authority, automation_id) in chunk {
+                let automation_address = automation_pda_with_id(*authority, *automation_id).0;
+                accounts.push(AccountMeta::new(*authority, false));
+                accounts.push(AccountMeta::new(miner_pda(*authority).0, false));
+                accounts.push(AccountMeta::new(automation_address, false));
+            }
+            Instruction {
+                program_id: crate::ID,
+                accounts,
+                data: CheckpointMany {}.to_bytes(),
+            }
+        })
+        .collect()
+}
+
+// let [signer_info, board_info, config_info, system_program, ore_program] = accounts else {
+
 pub fn set_admin(signer: Pubkey, admin: Pubkey) -> Instruction {
+    let board_address = board_pda().0;
     let config_address = config_pda().0;
     Instruction {
         program_id: crate::ID,
         accounts: vec![
             AccountMeta::new(signer, true),
+            AccountMeta::new(board_address, false),
             AccountMeta::new(config_address, false),
             AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(crate::ID, false),
         ],
         data: SetAdmin {
             admin: admin.to_bytes(),
@@ -369,6 +824,169 @@ pub fn set_admin(signer: Pubkey, admin: Pubkey) -> Instruction {
     }
 }
 
+// let [signer_info, board_info, config_info, ore_program] = accounts else {
+
+pub fn accept_admin(signer: Pubkey) -> Instruction {
+    let board_address = board_pda().0;
+    let config_address = config_pda().0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(board_address, false),
+            AccountMeta::new(config_address, false),
+            AccountMeta::new_readonly(crate::ID, false),
+        ],
+        data: AcceptAdmin {}.to_bytes(),
+    }
+}
+
+// let [signer_info, config_info, system_program] = accounts else {
+
+pub fn set_fee_collector(signer: Pubkey, fee_collector: Pubkey) -> Instruction {
+    let config_address = config_pda().0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(config_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: SetFeeCollector {
+            fee_collector: fee_collector.to_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+pub fn set_fee_rate(signer: Pubkey, fee_rate: u64) -> Instruction {
+    let config_address = config_pda().0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(config_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: SetFeeRate {
+            fee_rate: fee_rate.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+pub fn set_admin_fee(signer: Pubkey, admin_fee: u64) -> Instruction {
+    let config_address = config_pda().0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(config_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: SetAdminFee {
+            admin_fee: admin_fee.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+// let [signer_info, config_info, system_program] = accounts else {
+
+pub fn set_round_timing(signer: Pubkey, round_slots: u64, intermission_slots: u64) -> Instruction {
+    let config_address = config_pda().0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(config_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: SetRoundTiming {
+            round_slots: round_slots.to_le_bytes(),
+            intermission_slots: intermission_slots.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+// Round timing changes apply from the next round, so these use the live config only for rounds that have not started.
+
+pub fn round_end_slot(config: &Config, board: &Board, slot: u64) -> u64 {
+    if board.end_slot == u64::MAX {
+        slot.saturating_add(config.round_slots())
+    } else {
+        board.end_slot
+    }
+}
+
+pub fn round_reset_slot(config: &Config, board: &Board, round: &Round, slot: u64) -> u64 {
+    let intermission_slots = if board.end_slot == u64::MAX {
+        config.intermission_slots()
+    } else {
+        round.intermission_slots
+    };
+    round_end_slot(config, board, slot).saturating_add(intermission_slots)
+}
+
+// let [signer_info, board_info, config_info, var_info, system_program, entropy_program] = accounts else {
+
+pub fn set_var(signer: Pubkey, var: Pubkey, entropy_program: Pubkey) -> Instruction {
+    let board_address = board_pda().0;
+    let config_address = config_pda().0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(board_address, false),
+            AccountMeta::new(config_address, false),
+            AccountMeta::new_readonly(var, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(entropy_program, false),
+        ],
+        data: SetVar {}.to_bytes(),
+    }
+}
+
+// let [signer_info, config_info, system_program] = accounts else {
+
+pub fn set_withdraw_config(signer: Pubkey, cutoff_slots: u64, penalty_rate: u64) -> Instruction {
+    let config_address = config_pda().0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(config_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: SetWithdrawConfig {
+            cutoff_slots: cutoff_slots.to_le_bytes(),
+            penalty_rate: penalty_rate.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+// let [signer_info, board_info, config_info, system_program] = accounts else {
+
+pub fn set_mass_round(signer: Pubkey, mass_round_id: u64) -> Instruction {
+    let board_address = board_pda().0;
+    let config_address = config_pda().0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(board_address, false),
+            AccountMeta::new(config_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: SetMassRound {
+            mass_round_id: mass_round_id.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
 pub fn new_var(
     signer: Pubkey,
     provider: Pubkey,
@@ -2,7 +2,8 @@ use ore_mint_api::consts::ONE_ORE;
 use serde::{Deserialize, Serialize};
 use steel::*;
 
-use crate::state::{automation_pda, OreAccount};
+use crate::consts::ONE_DAY;
+use crate::state::{automation_pda_with_id, OreAccount};
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize)]
@@ -27,6 +28,7 @@ pub struct Automation {
 
     /// The mask of squares this automation should deploy to if preferred strategy.
     /// If strategy is Random, first bit is used to determine how many squares to deploy to.
+    /// If strategy is ExpectedValue, the mask holds the squares eligible for selection.
     pub mask: u64,
 
     /// Whether or not to auto-reload SOL winnings into the automation balance.
@@ -40,6 +42,29 @@ pub struct Automation {
 
     /// Conditions that must be met for the automation to deploy.
     pub conditions: AutomationConditions,
+
+    /// Limits after which the automation stops deploying.
+    pub limits: AutomationLimits,
+
+    /// The number of rounds this automation has deployed in since its limits were last set.
+    pub rounds_deployed: u64,
+
+    /// The amount of SOL deployed in the current daily window.
+    pub daily_deployed: u64,
+
+    /// The unix timestamp at which the current daily window started.
+    pub daily_window_start: i64,
+
+    /// The index of this automation among the authority's automations.
+    /// Automation 0 uses the legacy single-automation address.
+    pub id: u64,
+
+    /// The ID of the last round this automation deployed in.
+    /// New automations start at u64::MAX, so they have not deployed in any round.
+    pub last_round_id: u64,
+
+    /// Whether the executor must hold an unpaused registry entry whose fee ceiling covers the fee on every deploy.
+    pub require_registered_executor: u64,
 }
 
 /// Conditions that gate whether an automation deploys in a given round.
@@ -66,8 +91,43 @@ pub struct AutomationConditions {
     /// Default: u16::MAX (no preference).
     pub solo_tiles: u16,
 
+    /// Version of the Random strategy's square selection. See [`crate::strategy::StrategyVersion`].
+    /// Default: 0 (V1).
+    pub strategy_version: u16,
+
+    /// Number of squares the ExpectedValue strategy deploys to, chosen from the mask.
+    /// Default: 0 (unused).
+    pub ev_squares: u16,
+
+    /// How the ExpectedValue strategy ranks squares. See [`crate::strategy::ExpectedValueMode`].
+    /// Default: 0 (least crowded).
+    pub ev_mode: u16,
+
     /// Unused buffer space.
-    pub _buffer: u64,
+    pub _buffer: u16,
+}
+
+/// Limits that stop an automation from deploying. A value of zero disables the limit.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable, Serialize, Deserialize)]
+pub struct AutomationLimits {
+    /// The slot at which the automation expires.
+    pub expires_at_slot: u64,
+
+    /// The round ID at which the automation expires.
+    pub expires_at_round: u64,
+
+    /// Max number of rounds to deploy in.
+    pub max_rounds: u64,
+
+    /// Max amount of SOL to deploy per day.
+    pub max_daily_deploy: u64,
+
+    /// Production cost (lamports per whole ORE) above which the automation stops deploying.
+    pub stop_loss_production_cost: u64,
+
+    /// Number of rounds to deploy in before the stop-loss applies.
+    pub stop_loss_min_rounds: u64,
 }
 
 #[repr(u8)]
@@ -77,6 +137,61 @@ pub enum AutomationStrategy {
     Preferred = 1,
     Discretionary = 2,
     DiscretionaryBps = 3,
+    ExpectedValue = 4,
+}
+
+/// Reasons a conditional automation skips deploying in a given round.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AutomationSkipReason {
+    /// The motherlode is below the automation's minimum.
+    MotherlodeBelowMin,
+
+    /// The motherlode is above the automation's maximum.
+    MotherlodeAboveMax,
+
+    /// The production cost EMA is above the automation's maximum.
+    ProductionCostAboveMax,
+
+    /// The deploy would exceed the automation's daily budget.
+    DailyBudgetExceeded,
+
+    /// The miner's round is already funded manually or by another automation.
+    FundedElsewhere,
+}
+
+impl AutomationSkipReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::MotherlodeBelowMin => "motherlode below min",
+            Self::MotherlodeAboveMax => "motherlode above max",
+            Self::ProductionCostAboveMax => "production cost above max",
+            Self::DailyBudgetExceeded => "daily budget exceeded",
+            Self::FundedElsewhere => "round funded elsewhere",
+        }
+    }
+}
+
+/// Reasons an automation stops deploying for good.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AutomationStopReason {
+    /// The automation has passed its expiry slot or round.
+    Expired,
+
+    /// The automation has deployed in its maximum number of rounds.
+    MaxRounds,
+
+    /// The automation's production cost has exceeded its stop-loss.
+    StopLoss,
+}
+
+impl AutomationStopReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Expired => "expired",
+            Self::MaxRounds => "max rounds reached",
+            Self::StopLoss => "stop-loss triggered",
+        }
+    }
 }
 
 impl AutomationStrategy {
@@ -93,6 +208,9 @@ impl Default for AutomationConditions {
             max_motherlode: u16::MAX,
             split_tiles: 0,
             solo_tiles: 0,
+            strategy_version: 0,
+            ev_squares: 0,
+            ev_mode: 0,
             _buffer: 0,
         }
     }
@@ -106,7 +224,10 @@ impl AutomationConditions {
         bytes[10..12].copy_from_slice(&self.max_motherlode.to_le_bytes());
         bytes[12..14].copy_from_slice(&self.split_tiles.to_le_bytes());
         bytes[14..16].copy_from_slice(&self.solo_tiles.to_le_bytes());
-        bytes[16..24].copy_from_slice(&self._buffer.to_le_bytes());
+        bytes[16..18].copy_from_slice(&self.strategy_version.to_le_bytes());
+        bytes[18..20].copy_from_slice(&self.ev_squares.to_le_bytes());
+        bytes[20..22].copy_from_slice(&self.ev_mode.to_le_bytes());
+        bytes[22..24].copy_from_slice(&self._buffer.to_le_bytes());
         bytes
     }
 
@@ -117,14 +238,60 @@ impl AutomationConditions {
             max_motherlode: u16::from_le_bytes(bytes[10..12].try_into().unwrap()),
             split_tiles: u16::from_le_bytes(bytes[12..14].try_into().unwrap()),
             solo_tiles: u16::from_le_bytes(bytes[14..16].try_into().unwrap()),
-            _buffer: u64::from_le_bytes(bytes[16..24].try_into().unwrap()),
+            strategy_version: u16::from_le_bytes(bytes[16..18].try_into().unwrap()),
+            ev_squares: u16::from_le_bytes(bytes[18..20].try_into().unwrap()),
+            ev_mode: u16::from_le_bytes(bytes[20..22].try_into().unwrap()),
+            _buffer: u16::from_le_bytes(bytes[22..24].try_into().unwrap()),
+        }
+    }
+
+    /// Returns the reason the automation should skip deploying, if any.
+    /// The motherlode is denominated in grams and the production cost in lamports per whole ORE.
+    pub fn skip_reason(
+        &self,
+        motherlode: u64,
+        production_cost_ema: u64,
+    ) -> Option<AutomationSkipReason> {
+        if motherlode < self.min_motherlode as u64 * ONE_ORE {
+            return Some(AutomationSkipReason::MotherlodeBelowMin);
+        }
+        if motherlode > self.max_motherlode as u64 * ONE_ORE {
+            return Some(AutomationSkipReason::MotherlodeAboveMax);
+        }
+        if production_cost_ema > self.max_production_cost {
+            return Some(AutomationSkipReason::ProductionCostAboveMax);
+        }
+        None
+    }
+}
+
+impl AutomationLimits {
+    pub fn to_bytes(&self) -> [u8; 48] {
+        let mut bytes = [0; 48];
+        bytes[0..8].copy_from_slice(&self.expires_at_slot.to_le_bytes());
+        bytes[8..16].copy_from_slice(&self.expires_at_round.to_le_bytes());
+        bytes[16..24].copy_from_slice(&self.max_rounds.to_le_bytes());
+        bytes[24..32].copy_from_slice(&self.max_daily_deploy.to_le_bytes());
+        bytes[32..40].copy_from_slice(&self.stop_loss_production_cost.to_le_bytes());
+        bytes[40..48].copy_from_slice(&self.stop_loss_min_rounds.to_le_bytes());
+        bytes
+    }
+
+    pub fn from_bytes(bytes: [u8; 48]) -> Self {
+        Self {
+            expires_at_slot: u64::from_le_bytes(bytes[0..8].try_into().unwrap()),
+            expires_at_round: u64::from_le_bytes(bytes[8..16].try_into().unwrap()),
+            max_rounds: u64::from_le_bytes(bytes[16..24].try_into().unwrap()),
+            max_daily_deploy: u64::from_le_bytes(bytes[24..32].try_into().unwrap()),
+            stop_loss_production_cost: u64::from_le_bytes(bytes[32..40].try_into().unwrap()),
+            stop_loss_min_rounds: u64::from_le_bytes(bytes[40..48].try_into().unwrap()),
         }
     }
 }
 
 impl Automation {
     pub fn pda(&self) -> (Pubkey, u8) {
-        automation_pda(self.authority)
+        automation_pda_with_id(self.authority, self.id)
     }
 
     pub fn min_fee(&self, deploy_amount: u64) -> u64 {
@@ -135,6 +302,37 @@ impl Automation {
         }
     }
 
+    /// Returns the minimum balance required to deploy on one square, including the executor fee.
+    pub fn min_balance(&self) -> u64 {
+        self.amount + self.min_fee(self.amount)
+    }
+
+    /// Returns the number of squares the automation deploys to each round.
+    /// Discretionary strategies may deploy to a single square.
+    pub fn squares_per_round(&self) -> u64 {
+        let mask_squares = (self.mask & 0x1FF_FFFF).count_ones() as u64;
+        let squares = match AutomationStrategy::from_u64(self.strategy) {
+            AutomationStrategy::Preferred => mask_squares,
+            AutomationStrategy::Random => {
+                let conditions = &self.conditions;
+                if conditions.solo_tiles > 0 || conditions.split_tiles > 0 {
+                    (conditions.solo_tiles as u64 + conditions.split_tiles as u64).min(25)
+                } else {
+                    mask_squares
+                }
+            }
+            AutomationStrategy::ExpectedValue => self.conditions.ev_squares as u64,
+            AutomationStrategy::Discretionary | AutomationStrategy::DiscretionaryBps => 1,
+        };
+        squares.max(1)
+    }
+
+    /// Returns the minimum balance required to deploy a full round, including the executor fee.
+    pub fn min_round_balance(&self) -> u64 {
+        let amount = self.amount * self.squares_per_round();
+        amount + self.min_fee(amount)
+    }
+
     pub fn production_cost(&self) -> u64 {
         if self.total_ore_earned == 0 {
             return 0;
@@ -142,6 +340,420 @@ impl Automation {
         ((self.total_sol_spent as u128) * (ONE_ORE as u128) / (self.total_ore_earned as u128))
             as u64
     }
+
+    /// Returns the reason the automation should stop deploying for good, if any.
+    pub fn stop_reason(&self, slot: u64, round_id: u64) -> Option<AutomationStopReason> {
+        let limits = &self.limits;
+        if (limits.expires_at_slot > 0 && slot >= limits.expires_at_slot)
+            || (limits.expires_at_round > 0 && round_id >= limits.expires_at_round)
+        {
+            return Some(AutomationStopReason::Expired);
+        }
+        if limits.max_rounds > 0 && self.rounds_deployed >= limits.max_rounds {
+            return Some(AutomationStopReason::MaxRounds);
+        }
+        if limits.stop_loss_production_cost > 0
+            && self.rounds_deployed >= limits.stop_loss_min_rounds
+            && self.total_sol_spent > 0
+        {
+            // An automation that has spent SOL without earning any ORE has an unbounded production cost.
+            if self.total_ore_earned == 0
+                || self.production_cost() > limits.stop_loss_production_cost
+            {
+                return Some(AutomationStopReason::StopLoss);
+            }
+        }
+        None
+    }
+
+    /// Returns the amount of SOL deployed in the daily window containing the given timestamp.
+    pub fn daily_deployed_at(&self, ts: i64) -> u64 {
+        if ts >= self.daily_window_start + ONE_DAY {
+            0
+        } else {
+            self.daily_deployed
+        }
+    }
+
+    /// Returns true if deploying the given amount would exceed the daily budget.
+    pub fn exceeds_daily_budget(&self, amount: u64, ts: i64) -> bool {
+        self.limits.max_daily_deploy > 0
+            && self.daily_deployed_at(ts) + amount > self.limits.max_daily_deploy
+    }
+
+    /// Returns true if this automation has not yet deployed in the given round.
+    /// Executor fees and round limits are charged once per automation per round.
+    pub fn is_new_round(&self, round_id: u64) -> bool {
+        self.last_round_id != round_id
+    }
+
+    /// Returns the executor fee owed for deploying the given amount in the given round.
+    /// The fee is charged on the automation's first deploy of each round.
+    pub fn deploy_fee(&self, amount: u64, round_id: u64) -> u64 {
+        if amount > 0 && self.is_new_round(round_id) {
+            self.min_fee(amount)
+        } else {
+            0
+        }
+    }
+
+    /// Records a deploy against the automation's round and daily limits.
+    pub fn record_deploy(&mut self, amount: u64, is_new_round: bool, ts: i64) {
+        if ts >= self.daily_window_start + ONE_DAY {
+            self.daily_window_start = ts;
+            self.daily_deployed = 0;
+        }
+        self.daily_deployed += amount;
+        if is_new_round {
+            self.rounds_deployed += 1;
+        }
+    }
+
+    /// Returns a withdrawn deploy to the automation's balance, and reverts its spend and limits.
+    /// If the withdrawal leaves nothing deployed in the round, the round is no longer counted.
+    pub fn record_withdraw(&mut self, amount: u64, refund: u64, is_round_withdrawn: bool, ts: i64) {
+        self.balance += refund;
+        self.total_sol_spent = self.total_sol_spent.saturating_sub(refund);
+        if ts < self.daily_window_start + ONE_DAY {
+            self.daily_deployed = self.daily_deployed.saturating_sub(amount);
+        }
+        if is_round_withdrawn {
+            self.rounds_deployed = self.rounds_deployed.saturating_sub(1);
+            self.last_round_id = u64::MAX;
+        }
+    }
 }
 
 account!(OreAccount, Automation);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consts::MANUAL_AUTOMATION_ID;
+    use crate::state::Miner;
+
+    #[test]
+    fn test_pda_uses_legacy_address_for_id_zero() {
+        let mut automation = Automation::zeroed();
+        automation.authority = Pubkey::new_unique();
+        let legacy = crate::state::automation_pda(automation.authority);
+        assert_eq!(automation.pda(), legacy);
+        automation.id = 1;
+        let indexed = automation.pda();
+        assert_ne!(indexed, legacy);
+        automation.id = 2;
+        assert_ne!(automation.pda(), indexed);
+    }
+
+    #[test]
+    fn test_min_balance_includes_fee() {
+        let mut automation = Automation::zeroed();
+        automation.amount = 1_000_000;
+        automation.fee = 5_000;
+        automation.strategy = AutomationStrategy::Preferred as u64;
+        assert_eq!(automation.min_balance(), 1_005_000);
+
+        // Bps fees are charged on the deploy amount.
+        automation.fee = 50;
+        automation.strategy = AutomationStrategy::DiscretionaryBps as u64;
+        assert_eq!(automation.min_balance(), 1_005_000);
+    }
+
+    #[test]
+    fn test_min_round_balance_covers_selected_squares() {
+        let mut automation = Automation::zeroed();
+        automation.amount = 1_000_000;
+        automation.fee = 5_000;
+        automation.mask = 0b1011;
+        automation.strategy = AutomationStrategy::Preferred as u64;
+        assert_eq!(automation.min_round_balance(), 3_005_000);
+
+        // Random automations deploy to their solo and split tiles if set.
+        automation.strategy = AutomationStrategy::Random as u64;
+        assert_eq!(automation.min_round_balance(), 3_005_000);
+        automation.conditions.solo_tiles = 2;
+        automation.conditions.split_tiles = 3;
+        assert_eq!(automation.min_round_balance(), 5_005_000);
+
+        // ExpectedValue automations deploy to their chosen number of squares.
+        automation.strategy = AutomationStrategy::ExpectedValue as u64;
+        automation.conditions.ev_squares = 4;
+        assert_eq!(automation.min_round_balance(), 4_005_000);
+
+        // Discretionary automations need only one square, with bps fees on the deploy amount.
+        automation.fee = 50;
+        automation.strategy = AutomationStrategy::DiscretionaryBps as u64;
+        assert_eq!(automation.min_round_balance(), automation.min_balance());
+    }
+
+    #[test]
+    fn test_stop_reason_default_limits_never_stop() {
+        let mut automation = Automation::zeroed();
+        automation.rounds_deployed = 1_000;
+        automation.total_sol_spent = 1_000_000_000;
+        assert_eq!(automation.stop_reason(u64::MAX, u64::MAX), None);
+        assert!(!automation.exceeds_daily_budget(u64::MAX, 0));
+    }
+
+    #[test]
+    fn test_stop_reason_expiry_and_max_rounds() {
+        let mut automation = Automation::zeroed();
+        automation.limits.expires_at_slot = 100;
+        assert_eq!(automation.stop_reason(99, 0), None);
+        assert_eq!(
+            automation.stop_reason(100, 0),
+            Some(AutomationStopReason::Expired)
+        );
+
+        automation.limits = AutomationLimits {
+            expires_at_round: 10,
+            max_rounds: 3,
+            ..AutomationLimits::default()
+        };
+        assert_eq!(
+            automation.stop_reason(0, 10),
+            Some(AutomationStopReason::Expired)
+        );
+        automation.rounds_deployed = 2;
+        assert_eq!(automation.stop_reason(0, 9), None);
+        automation.rounds_deployed = 3;
+        assert_eq!(
+            automation.stop_reason(0, 9),
+            Some(AutomationStopReason::MaxRounds)
+        );
+    }
+
+    #[test]
+    fn test_stop_reason_stop_loss_after_min_rounds() {
+        let mut automation = Automation::zeroed();
+        automation.limits.stop_loss_production_cost = 1_000_000;
+        automation.limits.stop_loss_min_rounds = 5;
+        automation.total_sol_spent = 2_000_000;
+        automation.total_ore_earned = ONE_ORE;
+        automation.rounds_deployed = 4;
+        assert_eq!(automation.stop_reason(0, 0), None);
+        automation.rounds_deployed = 5;
+        assert_eq!(
+            automation.stop_reason(0, 0),
+            Some(AutomationStopReason::StopLoss)
+        );
+
+        // Production cost at the threshold does not trigger the stop-loss.
+        automation.total_sol_spent = 1_000_000;
+        assert_eq!(automation.stop_reason(0, 0), None);
+
+        // Spending SOL without earning any ORE does.
+        automation.total_ore_earned = 0;
+        assert_eq!(
+            automation.stop_reason(0, 0),
+            Some(AutomationStopReason::StopLoss)
+        );
+    }
+
+    #[test]
+    fn test_daily_budget_resets_each_window() {
+        let mut automation = Automation::zeroed();
+        automation.limits.max_daily_deploy = 1_000;
+        automation.record_deploy(600, true, ONE_DAY);
+        assert_eq!(automation.rounds_deployed, 1);
+        assert_eq!(automation.daily_window_start, ONE_DAY);
+        assert!(!automation.exceeds_daily_budget(400, ONE_DAY + 60));
+        assert!(automation.exceeds_daily_budget(401, ONE_DAY + 60));
+
+        // Top-ups within the same round do not count as a new round.
+        automation.record_deploy(400, false, ONE_DAY + 60);
+        assert_eq!(automation.rounds_deployed, 1);
+        assert!(automation.exceeds_daily_budget(1, 2 * ONE_DAY - 1));
+
+        // The budget is restored once the window has elapsed.
+        assert!(!automation.exceeds_daily_budget(1_000, 2 * ONE_DAY));
+        automation.record_deploy(1_000, true, 2 * ONE_DAY);
+        assert_eq!(automation.daily_deployed, 1_000);
+        assert_eq!(automation.daily_window_start, 2 * ONE_DAY);
+    }
+
+    #[test]
+    fn test_deploy_fee_charged_once_per_automation_per_round() {
+        let mut automation = Automation::zeroed();
+        automation.fee = 5_000;
+        automation.last_round_id = u64::MAX;
+        assert_eq!(automation.deploy_fee(1_000, 7), 5_000);
+        assert_eq!(automation.deploy_fee(0, 7), 0);
+
+        // Top-ups in the same round are not charged again.
+        automation.last_round_id = 7;
+        assert!(!automation.is_new_round(7));
+        assert_eq!(automation.deploy_fee(1_000, 7), 0);
+        assert_eq!(automation.deploy_fee(1_000, 8), 5_000);
+    }
+
+    #[test]
+    fn test_two_automations_deploy_in_same_round() {
+        let authority = Pubkey::new_unique();
+        let mut first = Automation::zeroed();
+        first.authority = authority;
+        first.fee = 5_000;
+        first.last_round_id = u64::MAX;
+        let mut second = first;
+        second.id = 1;
+
+        // The first automation funds the miner's round.
+        let mut miner = Miner::zeroed();
+        miner.authority = authority;
+        miner.round_id = 7;
+        assert!(!miner.is_funded_by_other(7, second.id));
+        assert_eq!(first.deploy_fee(1_000, 7), 5_000);
+        first.record_deploy(1_000, first.is_new_round(7), ONE_DAY);
+        first.last_round_id = 7;
+        miner.deployed[0] = 1_000;
+        miner.automation_id = first.id;
+
+        // Neither the second automation nor a manual deploy may join the round, but the first may top up.
+        assert!(miner.is_funded_by_other(7, second.id));
+        assert!(miner.is_funded_by_other(7, MANUAL_AUTOMATION_ID));
+        assert!(!miner.is_funded_by_other(7, first.id));
+        assert_eq!(first.deploy_fee(1_000, 7), 0);
+        assert_eq!(first.rounds_deployed, 1);
+
+        // The second automation is charged and counted in its own first round.
+        assert!(!miner.is_funded_by_other(8, second.id));
+        assert_eq!(second.deploy_fee(1_000, 8), 5_000);
+        second.record_deploy(1_000, second.is_new_round(8), ONE_DAY);
+        assert_eq!(second.rounds_deployed, 1);
+    }
+
+    #[test]
+    fn test_manual_and_automation_deploys_do_not_mix() {
+        let authority = Pubkey::new_unique();
+        let mut miner = Miner::zeroed();
+        miner.authority = authority;
+        miner.round_id = 7;
+        miner.deployed[0] = 1_000;
+        miner.automation_id = MANUAL_AUTOMATION_ID;
+
+        // A manually funded round is closed to automations, including the legacy automation 0.
+        assert!(miner.is_manually_funded());
+        assert!(miner.is_funded_by_other(7, 0));
+        assert!(miner.is_funded_by_other(7, 1));
+        assert!(!miner.is_funded_by_other(7, MANUAL_AUTOMATION_ID));
+        assert_eq!(
+            miner.automation_address(),
+            crate::state::automation_pda(authority).0
+        );
+
+        // An automation funded round is closed to manual deploys.
+        miner.automation_id = 0;
+        assert!(!miner.is_manually_funded());
+        assert!(miner.is_funded_by_other(7, MANUAL_AUTOMATION_ID));
+        assert!(!miner.is_funded_by_other(7, 0));
+        miner.automation_id = 2;
+        assert_eq!(
+            miner.automation_address(),
+            automation_pda_with_id(authority, 2).0
+        );
+    }
+
+    #[test]
+    fn test_record_withdraw_reverts_deploy() {
+        let mut automation = Automation::zeroed();
+        automation.limits.max_daily_deploy = 1_000;
+        automation.balance = 4_000;
+        automation.total_sol_spent = 1_000;
+        automation.record_deploy(1_000, true, ONE_DAY);
+        automation.last_round_id = 7;
+
+        // A partial withdrawal refunds the balance less the penalty and restores the daily budget.
+        automation.record_withdraw(400, 360, false, ONE_DAY + 60);
+        assert_eq!(automation.balance, 4_360);
+        assert_eq!(automation.total_sol_spent, 640);
+        assert_eq!(automation.daily_deployed, 600);
+        assert!(!automation.exceeds_daily_budget(400, ONE_DAY + 60));
+        assert_eq!(automation.rounds_deployed, 1);
+        assert!(!automation.is_new_round(7));
+
+        // Withdrawing the rest of the round no longer counts it.
+        automation.record_withdraw(600, 540, true, ONE_DAY + 60);
+        assert_eq!(automation.balance, 4_900);
+        assert_eq!(automation.total_sol_spent, 100);
+        assert_eq!(automation.daily_deployed, 0);
+        assert_eq!(automation.rounds_deployed, 0);
+        assert!(automation.is_new_round(7));
+    }
+
+    #[test]
+    fn test_limits_bytes_roundtrip() {
+        let limits = AutomationLimits {
+            expires_at_slot: 1,
+            expires_at_round: 2,
+            max_rounds: 3,
+            max_daily_deploy: 4,
+            stop_loss_production_cost: 5,
+            stop_loss_min_rounds: 6,
+        };
+        assert_eq!(AutomationLimits::from_bytes(limits.to_bytes()), limits);
+    }
+
+    #[test]
+    fn test_skip_reason_default_conditions_never_skip() {
+        let conditions = AutomationConditions::default();
+        assert_eq!(conditions.max_production_cost, u64::MAX);
+        assert_eq!(conditions.skip_reason(0, 0), None);
+        assert_eq!(conditions.skip_reason(100 * ONE_ORE, u64::MAX), None);
+    }
+
+    #[test]
+    fn test_skip_reason_production_cost_cap() {
+        let conditions = AutomationConditions {
+            max_production_cost: 1_000_000,
+            ..AutomationConditions::default()
+        };
+        assert_eq!(conditions.skip_reason(0, 0), None);
+        assert_eq!(conditions.skip_reason(0, 1_000_000), None);
+        assert_eq!(
+            conditions.skip_reason(0, 1_000_001),
+            Some(AutomationSkipReason::ProductionCostAboveMax)
+        );
+    }
+
+    #[test]
+    fn test_skip_reason_motherlode_bounds() {
+        let conditions = AutomationConditions {
+            min_motherlode: 10,
+            max_motherlode: 20,
+            ..AutomationConditions::default()
+        };
+        assert_eq!(
+            conditions.skip_reason(10 * ONE_ORE - 1, 0),
+            Some(AutomationSkipReason::MotherlodeBelowMin)
+        );
+        assert_eq!(conditions.skip_reason(10 * ONE_ORE, 0), None);
+        assert_eq!(conditions.skip_reason(20 * ONE_ORE, 0), None);
+        assert_eq!(
+            conditions.skip_reason(20 * ONE_ORE + 1, 0),
+            Some(AutomationSkipReason::MotherlodeAboveMax)
+        );
+    }
+
+    #[test]
+    fn test_skip_reason_motherlode_checked_before_production_cost() {
+        let conditions = AutomationConditions {
+            max_production_cost: 1_000_000,
+            min_motherlode: 10,
+            max_motherlode: 20,
+            ..AutomationConditions::default()
+        };
+        assert_eq!(
+            conditions.skip_reason(0, u64::MAX),
+            Some(AutomationSkipReason::MotherlodeBelowMin)
+        );
+        assert_eq!(
+            conditions.skip_reason(30 * ONE_ORE, u64::MAX),
+            Some(AutomationSkipReason::MotherlodeAboveMax)
+        );
+        assert_eq!(
+            conditions.skip_reason(15 * ONE_ORE, u64::MAX),
+            Some(AutomationSkipReason::ProductionCostAboveMax)
+        );
+        assert_eq!(conditions.skip_reason(15 * ONE_ORE, 1_000_000), None);
+    }
+}
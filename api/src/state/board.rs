@@ -24,6 +24,54 @@ impl Board {
     pub fn pda(&self) -> (Pubkey, u8) {
         board_pda()
     }
+
+    /// Returns the mass of a deployment, which is the amount weighted by the time remaining in the round.
+    /// Any non-zero deployment has a mass of at least 1.
+    pub fn mass(&self, amount: u64, slot: u64) -> u64 {
+        let duration = self.end_slot.saturating_sub(self.start_slot);
+        if amount == 0 || duration == 0 {
+            return 0;
+        }
+        let remaining = self.end_slot.saturating_sub(slot).min(duration);
+        let mass = (amount as u128 * remaining as u128 / duration as u128) as u64;
+        mass.max(1)
+    }
 }
 
 account!(OreAccount, Board);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board(start_slot: u64, end_slot: u64) -> Board {
+        Board {
+            round_id: 0,
+            start_slot,
+            end_slot,
+            production_cost_ema: 0,
+        }
+    }
+
+    #[test]
+    fn test_mass_decays_with_time_remaining() {
+        let board = board(1000, 1150);
+        assert_eq!(board.mass(1_500, 1000), 1_500);
+        assert_eq!(board.mass(1_500, 1075), 750);
+        assert_eq!(board.mass(1_500, 1149), 10);
+    }
+
+    #[test]
+    fn test_mass_has_floor_of_one() {
+        let board = board(1000, 1150);
+        assert_eq!(board.mass(0, 1000), 0);
+        assert_eq!(board.mass(1, 1149), 1);
+        assert_eq!(board.mass(1_500, 1150), 1);
+    }
+
+    #[test]
+    fn test_mass_is_capped_at_amount() {
+        let board = board(1000, 1150);
+        assert_eq!(board.mass(1_500, 900), 1_500);
+    }
+}
@@ -1,7 +1,14 @@
 use serde::{Deserialize, Serialize};
 use steel::*;
 
-use crate::state::{config_pda, OreAccount};
+use crate::{
+    consts::{
+        ADMIN_FEE, ADMIN_FEE_COLLECTOR, INTERMISSION_SLOTS, MAX_ADMIN_FEE, MAX_PROTOCOL_FEE,
+        PROTOCOL_FEE, ROUND_SLOTS, VAR_ADDRESS,
+    },
+    error::OreError,
+    state::{config_pda, OreAccount},
+};
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize)]
@@ -11,6 +18,21 @@ pub struct Config {
 
     /// The protocol config.
     pub protocol: ProtocolConfig,
+
+    /// The authority proposed to become the new admin authority.
+    /// Pubkey::default() if no handover is pending.
+    pub pending_admin: Pubkey,
+
+    /// The number of slots before the end of a round after which deployments can no longer be withdrawn.
+    /// Withdrawals are disabled while zero.
+    pub withdraw_cutoff_slots: u64,
+
+    /// The penalty charged on withdrawn SOL and sent to the treasury, in basis points.
+    pub withdraw_penalty_rate: u64,
+
+    /// The first round in which ORE rewards are weighted by mass instead of SOL.
+    /// Mass-weighted rewards are disabled while zero.
+    pub mass_round_id: u64,
 }
 
 #[repr(C)]
@@ -55,6 +77,183 @@ impl Config {
     pub fn pda() -> (Pubkey, u8) {
         config_pda()
     }
+
+    /// Returns the address of the admin fee collector, falling back to the default if unset.
+    pub fn fee_collector(&self) -> Pubkey {
+        if self.admin.fee_collector == Pubkey::default() {
+            ADMIN_FEE_COLLECTOR
+        } else {
+            self.admin.fee_collector
+        }
+    }
+
+    /// Returns the admin fee rate, falling back to the default if unset.
+    pub fn admin_fee_rate(&self) -> u64 {
+        match self.admin.fee_rate {
+            0 => ADMIN_FEE,
+            rate => rate,
+        }
+    }
+
+    /// Returns the protocol fee rate, falling back to the default if unset.
+    pub fn protocol_fee_rate(&self) -> u64 {
+        match self.protocol.fee_rate {
+            0 => PROTOCOL_FEE,
+            rate => rate,
+        }
+    }
+
+    /// Validates a new admin fee rate. Zero is rejected, since an unset rate falls back to the default.
+    pub fn validate_admin_fee_rate(rate: u64) -> Result<(), ProgramError> {
+        validate_fee_rate(rate, MAX_ADMIN_FEE)
+    }
+
+    /// Validates a new protocol fee rate. Zero is rejected, since an unset rate falls back to the default.
+    pub fn validate_protocol_fee_rate(rate: u64) -> Result<(), ProgramError> {
+        validate_fee_rate(rate, MAX_PROTOCOL_FEE)
+    }
+
+    /// Returns true if ORE rewards in the given round should be weighted by mass instead of SOL.
+    pub fn is_mass_round(&self, round_id: u64) -> bool {
+        self.mass_round_id > 0 && round_id >= self.mass_round_id
+    }
+
+    /// Returns the number of slots in a round, falling back to the default if unset.
+    pub fn round_slots(&self) -> u64 {
+        match self.protocol.round_slots {
+            0 => ROUND_SLOTS,
+            slots => slots,
+        }
+    }
+
+    /// Returns the number of slots in the intermission period, falling back to the default if unset.
+    pub fn intermission_slots(&self) -> u64 {
+        match self.protocol.intermission_slots {
+            0 => INTERMISSION_SLOTS,
+            slots => slots,
+        }
+    }
+
+    /// Returns the address of the active entropy var, falling back to the default if unset.
+    pub fn entropy_var(&self) -> Pubkey {
+        if self.protocol.entropy_var_address == Pubkey::default() {
+            VAR_ADDRESS
+        } else {
+            self.protocol.entropy_var_address
+        }
+    }
+
+    /// Returns the id of the entropy program, falling back to the default if unset.
+    pub fn entropy_program(&self) -> Pubkey {
+        if self.protocol.entropy_program_id == Pubkey::default() {
+            entropy_api::ID
+        } else {
+            self.protocol.entropy_program_id
+        }
+    }
+}
+
+fn validate_fee_rate(rate: u64, max: u64) -> Result<(), ProgramError> {
+    if rate == 0 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    if rate > max {
+        return Err(OreError::FeeRateTooHigh.into());
+    }
+    Ok(())
 }
 
 account!(OreAccount, Config);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fees_default_when_unset() {
+        let config = Config::zeroed();
+        assert_eq!(config.fee_collector(), ADMIN_FEE_COLLECTOR);
+        assert_eq!(config.admin_fee_rate(), ADMIN_FEE);
+        assert_eq!(config.protocol_fee_rate(), PROTOCOL_FEE);
+    }
+
+    #[test]
+    fn test_fees_from_config() {
+        let mut config = Config::zeroed();
+        let fee_collector = Pubkey::new_unique();
+        config.admin.fee_collector = fee_collector;
+        config.admin.fee_rate = 250;
+        config.protocol.fee_rate = 2_000;
+        assert_eq!(config.fee_collector(), fee_collector);
+        assert_eq!(config.admin_fee_rate(), 250);
+        assert_eq!(config.protocol_fee_rate(), 2_000);
+    }
+
+    #[test]
+    fn test_fee_rates_reject_zero_and_above_max() {
+        assert_eq!(
+            Config::validate_admin_fee_rate(0),
+            Err(ProgramError::InvalidInstructionData)
+        );
+        assert_eq!(Config::validate_admin_fee_rate(1), Ok(()));
+        assert_eq!(Config::validate_admin_fee_rate(MAX_ADMIN_FEE), Ok(()));
+        assert_eq!(
+            Config::validate_admin_fee_rate(MAX_ADMIN_FEE + 1),
+            Err(OreError::FeeRateTooHigh.into())
+        );
+        assert_eq!(
+            Config::validate_protocol_fee_rate(0),
+            Err(ProgramError::InvalidInstructionData)
+        );
+        assert_eq!(Config::validate_protocol_fee_rate(MAX_PROTOCOL_FEE), Ok(()));
+        assert_eq!(
+            Config::validate_protocol_fee_rate(MAX_PROTOCOL_FEE + 1),
+            Err(OreError::FeeRateTooHigh.into())
+        );
+    }
+
+    #[test]
+    fn test_mass_round_disabled_when_unset() {
+        let mut config = Config::zeroed();
+        assert!(!config.is_mass_round(0));
+        assert!(!config.is_mass_round(u64::MAX));
+        config.mass_round_id = 100;
+        assert!(!config.is_mass_round(99));
+        assert!(config.is_mass_round(100));
+        assert!(config.is_mass_round(101));
+    }
+
+    #[test]
+    fn test_round_timing_defaults_when_unset() {
+        let config = Config::zeroed();
+        assert_eq!(config.round_slots(), ROUND_SLOTS);
+        assert_eq!(config.intermission_slots(), INTERMISSION_SLOTS);
+    }
+
+    #[test]
+    fn test_round_timing_from_config() {
+        let mut config = Config::zeroed();
+        config.protocol.round_slots = 300;
+        config.protocol.intermission_slots = 50;
+        assert_eq!(config.round_slots(), 300);
+        assert_eq!(config.intermission_slots(), 50);
+    }
+
+    #[test]
+    fn test_entropy_defaults_when_unset() {
+        let config = Config::zeroed();
+        assert_eq!(config.entropy_var(), VAR_ADDRESS);
+        assert_eq!(config.entropy_program(), entropy_api::ID);
+    }
+
+    #[test]
+    fn test_entropy_from_config() {
+        let mut config = Config::zeroed();
+        let var = Pubkey::new_unique();
+        let program = Pubkey::new_unique();
+        config.protocol.entropy_var_address = var;
+        config.protocol.entropy_program_id = program;
+        assert_eq!(config.entropy_var(), var);
+        assert_eq!(config.entropy_program(), program);
+    }
+}
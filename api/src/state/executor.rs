@@ -0,0 +1,93 @@
+use steel::*;
+
+use crate::state::{executor_pda, AutomationStrategy, OreAccount};
+
+/// A registered third-party executor of automations.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct Executor {
+    /// The executor pubkey that signs deploys on behalf of automations.
+    pub authority: Pubkey,
+
+    /// The hash of the executor's display name.
+    pub name_hash: [u8; 32],
+
+    /// The max flat fee (lamports) the executor charges per deploy.
+    pub max_fee: u64,
+
+    /// The max fee (bps) the executor charges per deploy for DiscretionaryBps automations.
+    pub max_bps: u64,
+
+    /// Bitmask of the automation strategies the executor supports, indexed by strategy.
+    pub strategies: u64,
+
+    /// Whether or not the executor has paused operations.
+    pub paused: u64,
+
+    /// A contact URI for the executor, zero-padded.
+    pub uri: [u8; 64],
+}
+
+impl Executor {
+    pub fn pda(&self) -> (Pubkey, u8) {
+        executor_pda(self.authority)
+    }
+
+    /// Returns true if the executor supports the given strategy.
+    pub fn supports(&self, strategy: AutomationStrategy) -> bool {
+        self.strategies & (1 << strategy as u64) != 0
+    }
+
+    /// Returns the max fee the executor charges for the given strategy.
+    /// This is denominated in bps for DiscretionaryBps automations and lamports otherwise.
+    pub fn max_fee_for(&self, strategy: AutomationStrategy) -> u64 {
+        if strategy == AutomationStrategy::DiscretionaryBps {
+            self.max_bps
+        } else {
+            self.max_fee
+        }
+    }
+
+    /// Returns the contact URI as a string, with padding removed.
+    pub fn uri(&self) -> String {
+        let len = self
+            .uri
+            .iter()
+            .position(|&b| b == 0)
+            .unwrap_or(self.uri.len());
+        String::from_utf8_lossy(&self.uri[..len]).to_string()
+    }
+}
+
+account!(OreAccount, Executor);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_supports_and_max_fee_for() {
+        let mut executor = Executor::zeroed();
+        executor.max_fee = 5_000;
+        executor.max_bps = 50;
+        executor.strategies = (1 << AutomationStrategy::Random as u64)
+            | (1 << AutomationStrategy::DiscretionaryBps as u64);
+        assert!(executor.supports(AutomationStrategy::Random));
+        assert!(!executor.supports(AutomationStrategy::Preferred));
+        assert!(!executor.supports(AutomationStrategy::Discretionary));
+        assert!(executor.supports(AutomationStrategy::DiscretionaryBps));
+        assert_eq!(executor.max_fee_for(AutomationStrategy::Random), 5_000);
+        assert_eq!(
+            executor.max_fee_for(AutomationStrategy::DiscretionaryBps),
+            50
+        );
+    }
+
+    #[test]
+    fn test_uri_strips_padding() {
+        let mut executor = Executor::zeroed();
+        assert_eq!(executor.uri(), "");
+        executor.uri[..11].copy_from_slice(b"t.me/miner1");
+        assert_eq!(executor.uri(), "t.me/miner1");
+    }
+}
@@ -1,7 +1,10 @@
 use serde::{Deserialize, Serialize};
 use steel::*;
 
-use crate::state::{miner_pda, OreAccount, Treasury, DENOMINATOR_BPS};
+use crate::state::{
+    automation_pda_with_id, miner_pda, OreAccount, Round, Treasury, DENOMINATOR_BPS,
+    MANUAL_AUTOMATION_ID,
+};
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize)]
@@ -25,7 +28,8 @@ pub struct Miner {
     /// The amount of SOL deployed on each square, weighted by the time remaining when deployed.
     pub mass: [u64; 25],
 
-    /// The cumulative mass on each square prior to this miner's deployment on that square.
+    /// The cumulative weight on each square prior to this miner's deployment on that square.
+    /// Weight is SOL, or mass if the round is mass weighted.
     /// TODO: Rename to mass_cumulative.
     pub cumulative: [u64; 25],
 
@@ -63,6 +67,30 @@ pub struct Miner {
     /// The total amount of SOL this miner has mined across all blocks.
     /// TODO: Rename to lifetime_returned_sol.
     pub lifetime_rewards_sol: u64,
+
+    /// The cumulative weight on each square prior to this miner's top-up on that square.
+    pub topup_cumulative: [u64; 25],
+
+    /// The weight this miner added to each square by topping up.
+    /// Weight is SOL, or mass if the round is mass weighted.
+    pub topup: [u64; 25],
+
+    /// An optional account that may claim rewards on behalf of the authority.
+    pub claim_delegate: Pubkey,
+
+    /// The wallet the claim delegate must send rewards to.
+    pub claim_destination: Pubkey,
+
+    /// The ID of the automation that funded the miner's current round, or MANUAL_AUTOMATION_ID if funded manually.
+    /// SOL winnings are reloaded into this automation on checkpoint.
+    pub automation_id: u64,
+
+    /// The number of live automations with a non-zero ID deploying into this miner.
+    /// The legacy automation (ID 0) is tracked by its address instead.
+    pub automation_count: u64,
+
+    /// The SOL this miner deployed into its top-up range on each square.
+    pub topup_deployed: [u64; 25],
 }
 
 impl Miner {
@@ -70,6 +98,135 @@ impl Miner {
         miner_pda(self.authority)
     }
 
+    /// Returns the weight used to distribute ORE rewards on each square.
+    pub fn weights(&self, round: &Round) -> &[u64; 25] {
+        if round.is_mass_weighted() {
+            &self.mass
+        } else {
+            &self.deployed
+        }
+    }
+
+    /// Returns true if the top miner sample falls within one of this miner's ranges on the given square.
+    pub fn is_top_miner(&self, round: &Round, top_miner_sample: u64, square: usize) -> bool {
+        let weight = self.weights(round)[square] - self.topup[square];
+        let in_range = top_miner_sample >= self.cumulative[square]
+            && top_miner_sample < self.cumulative[square] + weight;
+        let in_topup_range = top_miner_sample >= self.topup_cumulative[square]
+            && top_miner_sample < self.topup_cumulative[square] + self.topup[square];
+        in_range || in_topup_range
+    }
+
+    /// Returns true if the miner's current round was funded by manual deploys.
+    pub fn is_manually_funded(&self) -> bool {
+        self.automation_id == MANUAL_AUTOMATION_ID
+    }
+
+    /// Returns the ID of the automation account that must accompany this miner.
+    /// Manually funded rounds use the legacy automation address.
+    pub fn automation_account_id(&self) -> u64 {
+        if self.is_manually_funded() {
+            0
+        } else {
+            self.automation_id
+        }
+    }
+
+    /// Returns the address of the automation account that must accompany this miner.
+    pub fn automation_address(&self) -> Pubkey {
+        automation_pda_with_id(self.authority, self.automation_account_id()).0
+    }
+
+    /// Returns true if the miner has already deployed in the given round with funds from another source.
+    /// The source is an automation ID, or MANUAL_AUTOMATION_ID for manual deploys.
+    /// Checkpoints reload winnings into a single automation, so sources may not mix within a round.
+    pub fn is_funded_by_other(&self, round_id: u64, automation_id: u64) -> bool {
+        self.round_id == round_id
+            && self.automation_id != automation_id
+            && self.deployed.iter().any(|&d| d > 0)
+    }
+
+    /// Records a top-up of the given SOL amount and weight on a square this miner has already deployed to.
+    /// Must be called before the round weight is updated. Returns false if the top-up cannot be
+    /// recorded as a contiguous range, since each miner may hold at most two ranges per square.
+    pub fn record_topup(&mut self, round: &Round, square: usize, amount: u64, weight: u64) -> bool {
+        let round_weight = round.weights()[square];
+        let initial_weight = self.weights(round)[square] - self.topup[square];
+        if self.topup[square] == 0 {
+            // Extend the initial range if no one has deployed since, otherwise open a second range.
+            if self.cumulative[square] + initial_weight != round_weight {
+                self.topup_cumulative[square] = round_weight;
+                self.topup[square] = weight;
+                self.topup_deployed[square] = amount;
+            }
+            true
+        } else if self.topup_cumulative[square] + self.topup[square] == round_weight {
+            self.topup[square] += weight;
+            self.topup_deployed[square] += amount;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Releases the given SOL amount from the end of this miner's last range on a square, returning
+    /// the mass released. Must be called before the round weight is updated. Returns None if the range
+    /// is not at the end of the square or holds less than the amount, since releasing it would shift
+    /// the ranges of miners who deployed after. Only the last miner to deploy on a square may withdraw.
+    pub fn release_range(&mut self, round: &Round, square: usize, amount: u64) -> Option<u64> {
+        let round_weight = round.weights()[square];
+        let deployed = self.deployed[square];
+        if amount > deployed {
+            return None;
+        }
+
+        // Mass is released in proportion to the SOL withdrawn from the whole square.
+        let mut mass = if amount == deployed {
+            self.mass[square]
+        } else {
+            (self.mass[square] as u128 * amount as u128 / deployed as u128) as u64
+        };
+
+        if self.topup[square] > 0 {
+            if self.topup_cumulative[square] + self.topup[square] != round_weight
+                || amount > self.topup_deployed[square]
+            {
+                return None;
+            }
+
+            // In mass-weighted rounds, release the top-up range's own mass per SOL.
+            let weight = if !round.is_mass_weighted() {
+                amount
+            } else if amount == self.topup_deployed[square] {
+                self.topup[square]
+            } else {
+                (self.topup[square] as u128 * amount as u128 / self.topup_deployed[square] as u128)
+                    as u64
+            };
+            if round.is_mass_weighted() {
+                mass = weight;
+            }
+            self.topup[square] -= weight;
+            self.topup_deployed[square] -= amount;
+            Some(mass)
+        } else if self.cumulative[square] + self.weights(round)[square] == round_weight {
+            Some(mass)
+        } else {
+            None
+        }
+    }
+
+    /// Returns true if the signer may claim this miner's rewards to the recipient.
+    /// The authority may claim to any recipient, and the claim delegate only to the claim destination.
+    pub fn can_claim(&self, signer: &Pubkey, recipient: &Pubkey) -> bool {
+        if *signer == self.authority {
+            return true;
+        }
+        self.claim_delegate != Pubkey::default()
+            && *signer == self.claim_delegate
+            && *recipient == self.claim_destination
+    }
+
     pub fn claim_ore(&mut self, clock: &Clock, treasury: &mut Treasury, bps: u64) -> (u64, u64) {
         self.update_rewards(treasury);
 
@@ -126,3 +283,224 @@ impl Miner {
 }
 
 account!(OreAccount, Miner);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_top_miner_uses_sol_range() {
+        let round = Round {
+            id: 1,
+            ..Round::zeroed()
+        };
+        let mut miner = Miner::zeroed();
+        miner.cumulative[0] = 100;
+        miner.deployed[0] = 50;
+        miner.mass[0] = 5;
+        assert!(!miner.is_top_miner(&round, 99, 0));
+        assert!(miner.is_top_miner(&round, 100, 0));
+        assert!(miner.is_top_miner(&round, 149, 0));
+        assert!(!miner.is_top_miner(&round, 150, 0));
+    }
+
+    #[test]
+    fn test_is_top_miner_uses_mass_range() {
+        let round = Round {
+            id: 1,
+            mass_weighted: 1,
+            ..Round::zeroed()
+        };
+        let mut miner = Miner::zeroed();
+        miner.cumulative[0] = 100;
+        miner.deployed[0] = 50;
+        miner.mass[0] = 5;
+        assert!(miner.is_top_miner(&round, 104, 0));
+        assert!(!miner.is_top_miner(&round, 105, 0));
+    }
+
+    #[test]
+    fn test_topup_extends_contiguous_range() {
+        let mut round = Round {
+            id: 1,
+            ..Round::zeroed()
+        };
+        let mut miner = Miner::zeroed();
+        miner.cumulative[0] = 100;
+        miner.deployed[0] = 50;
+        round.deployed[0] = 150;
+        assert!(miner.record_topup(&round, 0, 25, 25));
+        miner.deployed[0] += 25;
+        assert_eq!(miner.topup[0], 0);
+        assert!(miner.is_top_miner(&round, 174, 0));
+        assert!(!miner.is_top_miner(&round, 175, 0));
+    }
+
+    #[test]
+    fn test_topup_opens_second_range() {
+        let mut round = Round {
+            id: 1,
+            ..Round::zeroed()
+        };
+        let mut miner = Miner::zeroed();
+        miner.cumulative[0] = 100;
+        miner.deployed[0] = 50;
+        round.deployed[0] = 200;
+        assert!(miner.record_topup(&round, 0, 25, 25));
+        miner.deployed[0] += 25;
+        round.deployed[0] += 25;
+        assert!(miner.is_top_miner(&round, 149, 0));
+        assert!(!miner.is_top_miner(&round, 150, 0));
+        assert!(!miner.is_top_miner(&round, 199, 0));
+        assert!(miner.is_top_miner(&round, 200, 0));
+        assert!(miner.is_top_miner(&round, 224, 0));
+        assert!(!miner.is_top_miner(&round, 225, 0));
+
+        // A contiguous top-up extends the second range.
+        assert!(miner.record_topup(&round, 0, 10, 10));
+        miner.deployed[0] += 10;
+        round.deployed[0] += 10;
+        assert!(miner.is_top_miner(&round, 234, 0));
+
+        // A third, non-contiguous range cannot be recorded.
+        round.deployed[0] += 40;
+        assert!(!miner.record_topup(&round, 0, 10, 10));
+    }
+
+    #[test]
+    fn test_release_range_at_end_of_square() {
+        let mut round = Round {
+            id: 1,
+            ..Round::zeroed()
+        };
+        let mut miner = Miner::zeroed();
+        miner.cumulative[0] = 100;
+        miner.deployed[0] = 50;
+        round.deployed[0] = 150;
+        assert_eq!(miner.release_range(&round, 0, 20), Some(0));
+        assert_eq!(miner.release_range(&round, 0, 50), Some(0));
+        assert_eq!(miner.release_range(&round, 0, 51), None);
+    }
+
+    #[test]
+    fn test_release_range_rejects_inner_range() {
+        let mut round = Round {
+            id: 1,
+            ..Round::zeroed()
+        };
+        let mut miner = Miner::zeroed();
+        miner.cumulative[0] = 100;
+        miner.deployed[0] = 50;
+        round.deployed[0] = 200;
+        assert_eq!(miner.release_range(&round, 0, 10), None);
+
+        // Once topped up, only the top-up range at the end of the square can be released.
+        assert!(miner.record_topup(&round, 0, 25, 25));
+        miner.deployed[0] += 25;
+        round.deployed[0] += 25;
+        assert_eq!(miner.release_range(&round, 0, 26), None);
+        assert_eq!(miner.release_range(&round, 0, 25), Some(0));
+        assert_eq!(miner.topup[0], 0);
+    }
+
+    #[test]
+    fn test_release_range_partial_after_topup_in_mass_round() {
+        let mut round = Round {
+            id: 1,
+            mass_weighted: 1,
+            ..Round::zeroed()
+        };
+        let mut miner = Miner::zeroed();
+        miner.cumulative[0] = 100;
+        miner.deployed[0] = 50;
+        miner.mass[0] = 50;
+        round.mass[0] = 200;
+
+        // The top-up earns less mass per SOL than the initial deploy.
+        assert!(miner.record_topup(&round, 0, 40, 10));
+        miner.deployed[0] += 40;
+        miner.mass[0] += 10;
+        round.mass[0] += 10;
+
+        // A partial withdraw releases the top-up range's own mass, not the square's average.
+        assert_eq!(miner.release_range(&round, 0, 20), Some(5));
+        assert_eq!(miner.topup[0], 5);
+        assert_eq!(miner.topup_deployed[0], 20);
+        miner.deployed[0] -= 20;
+        miner.mass[0] -= 5;
+        round.mass[0] -= 5;
+
+        // The rest of the top-up range may be withdrawn, but not more.
+        assert_eq!(miner.release_range(&round, 0, 21), None);
+        assert_eq!(miner.release_range(&round, 0, 20), Some(5));
+        assert_eq!(miner.topup[0], 0);
+        assert_eq!(miner.topup_deployed[0], 0);
+    }
+
+    #[test]
+    fn test_release_range_rejects_non_last_depositor() {
+        let mut round = Round {
+            id: 1,
+            mass_weighted: 1,
+            ..Round::zeroed()
+        };
+
+        // The first miner deploys, then a second miner deploys after it on the same square.
+        let mut first = Miner::zeroed();
+        first.cumulative[0] = 0;
+        first.deployed[0] = 50;
+        first.mass[0] = 50;
+        let mut last = Miner::zeroed();
+        last.cumulative[0] = 50;
+        last.deployed[0] = 30;
+        last.mass[0] = 20;
+        round.mass[0] = 70;
+
+        // Only the last depositor holds the range at the end of the square.
+        assert_eq!(first.release_range(&round, 0, 10), None);
+        assert_eq!(first.release_range(&round, 0, 50), None);
+        assert_eq!(last.release_range(&round, 0, 15), Some(10));
+    }
+
+    #[test]
+    fn test_topup_uses_mass_range() {
+        let mut round = Round {
+            id: 1,
+            mass_weighted: 1,
+            ..Round::zeroed()
+        };
+        let mut miner = Miner::zeroed();
+        miner.cumulative[0] = 100;
+        miner.deployed[0] = 50;
+        miner.mass[0] = 5;
+        round.mass[0] = 120;
+        assert!(miner.record_topup(&round, 0, 30, 3));
+        miner.deployed[0] += 30;
+        miner.mass[0] += 3;
+        assert!(miner.is_top_miner(&round, 104, 0));
+        assert!(!miner.is_top_miner(&round, 105, 0));
+        assert!(miner.is_top_miner(&round, 122, 0));
+        assert!(!miner.is_top_miner(&round, 123, 0));
+    }
+
+    #[test]
+    fn test_can_claim() {
+        let authority = Pubkey::new_unique();
+        let delegate = Pubkey::new_unique();
+        let destination = Pubkey::new_unique();
+        let mut miner = Miner::zeroed();
+        miner.authority = authority;
+
+        // The authority may claim anywhere, and nobody else may claim without a delegate.
+        assert!(miner.can_claim(&authority, &Pubkey::new_unique()));
+        assert!(!miner.can_claim(&delegate, &destination));
+        assert!(!miner.can_claim(&Pubkey::default(), &Pubkey::default()));
+
+        // The delegate may only claim to the destination.
+        miner.claim_delegate = delegate;
+        miner.claim_destination = destination;
+        assert!(miner.can_claim(&delegate, &destination));
+        assert!(!miner.can_claim(&delegate, &delegate));
+        assert!(!miner.can_claim(&Pubkey::new_unique(), &destination));
+    }
+}
@@ -1,6 +1,7 @@
 mod automation;
 mod board;
 mod config;
+mod executor;
 mod miner;
 mod round;
 mod treasury;
@@ -8,6 +9,7 @@ mod treasury;
 pub use automation::*;
 pub use board::*;
 pub use config::*;
+pub use executor::*;
 pub use miner::*;
 pub use round::*;
 pub use treasury::*;
@@ -25,12 +27,24 @@ pub enum OreAccount {
     Treasury = 104,
     Board = 105,
     Round = 109,
+    Executor = 110,
 }
 
 pub fn automation_pda(authority: Pubkey) -> (Pubkey, u8) {
     Pubkey::find_program_address(&[AUTOMATION, &authority.to_bytes()], &crate::ID)
 }
 
+/// Returns the address of an indexed automation. Automation 0 is the legacy single-automation PDA.
+pub fn automation_pda_with_id(authority: Pubkey, id: u64) -> (Pubkey, u8) {
+    if id == 0 {
+        return automation_pda(authority);
+    }
+    Pubkey::find_program_address(
+        &[AUTOMATION, &authority.to_bytes(), &id.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
 pub fn board_pda() -> (Pubkey, u8) {
     Pubkey::find_program_address(&[BOARD], &crate::ID)
 }
@@ -43,6 +57,10 @@ pub fn config_pda() -> (Pubkey, u8) {
     Pubkey::find_program_address(&[CONFIG], &crate::ID)
 }
 
+pub fn executor_pda(authority: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[EXECUTOR, &authority.to_bytes()], &crate::ID)
+}
+
 pub fn miner_pda(authority: Pubkey) -> (Pubkey, u8) {
     Pubkey::find_program_address(&[MINER, &authority.to_bytes()], &crate::ID)
 }
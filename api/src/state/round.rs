@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use solana_program::keccak;
 use steel::*;
 
-use crate::state::{round_pda, OreAccount};
+use crate::state::{round_pda, OreAccount, DENOMINATOR_BPS};
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize)]
@@ -53,6 +53,21 @@ pub struct Round {
     /// The winner of the solo reward.
     /// TODO: Rename to winner.
     pub top_miner: Pubkey,
+
+    /// The admin fee rate charged in this round, in basis points.
+    pub admin_fee_rate: u64,
+
+    /// The protocol fee rate charged on losing squares in this round, in basis points.
+    pub protocol_fee_rate: u64,
+
+    /// The number of slots this round lasts once started.
+    pub round_slots: u64,
+
+    /// The number of slots between the end of this round and its reset.
+    pub intermission_slots: u64,
+
+    /// Whether or not ORE rewards in this round are weighted by mass instead of SOL.
+    pub mass_weighted: u64,
 }
 
 impl Round {
@@ -76,25 +91,62 @@ impl Round {
         (rng % 25) as usize
     }
 
+    /// Returns true if ORE rewards in this round are weighted by mass instead of SOL.
+    pub fn is_mass_weighted(&self) -> bool {
+        self.mass_weighted > 0
+    }
+
+    /// Returns the weight used to distribute ORE rewards on each square.
+    pub fn weights(&self) -> &[u64; 25] {
+        if self.is_mass_weighted() {
+            &self.mass
+        } else {
+            &self.deployed
+        }
+    }
+
     pub fn top_miner_sample(&self, rng: u64, winning_square: usize) -> u64 {
-        if self.deployed[winning_square] == 0 {
+        let weight = self.weights()[winning_square];
+        if weight == 0 {
             return 0;
         }
-        rng.reverse_bits() % self.deployed[winning_square]
+        rng.reverse_bits() % weight
     }
 
     pub fn calculate_fees(&self, winning_square: usize) -> (u64, u64) {
         let mut admin_fee = 0;
         let mut protocol_fee = 0;
-        for (i, &deployed) in self.deployed.iter().enumerate() {
-            if deployed > 0 {
-                let sq_admin = ((deployed / 100) as u64).max(1);
-                admin_fee += sq_admin;
-                if i != winning_square {
-                    protocol_fee += ((deployed.saturating_sub(sq_admin) / 10) as u64).max(1);
-                }
-            }
+        for i in 0..25 {
+            let (sq_admin, sq_protocol) = self.square_fees(i, winning_square);
+            admin_fee += sq_admin;
+            protocol_fee += sq_protocol;
+        }
+        (admin_fee, protocol_fee)
+    }
+
+    /// Returns the admin and protocol fees charged on a square.
+    /// The protocol fee is charged on losing squares only, after the admin fee.
+    pub fn square_fees(&self, square: usize, winning_square: usize) -> (u64, u64) {
+        let deployed = self.deployed[square];
+        if deployed == 0 {
+            return (0, 0);
         }
+        let admin_fee = fee(deployed, self.admin_fee_rate);
+        let protocol_fee = if square == winning_square {
+            0
+        } else {
+            fee(deployed.saturating_sub(admin_fee), self.protocol_fee_rate)
+        };
+        (admin_fee, protocol_fee)
+    }
+
+    /// Returns the admin fee, and the protocol fee charged if the square loses, on a new deployment.
+    pub fn deploy_fees(&self, amount: u64) -> (u64, u64) {
+        if amount == 0 {
+            return (0, 0);
+        }
+        let admin_fee = fee(amount, self.admin_fee_rate);
+        let protocol_fee = fee(amount.saturating_sub(admin_fee), self.protocol_fee_rate);
         (admin_fee, protocol_fee)
     }
 
@@ -164,11 +216,20 @@ impl Round {
     }
 }
 
+/// Returns the fee on an amount at the given rate, with a minimum of 1 if the rate is non-zero.
+fn fee(amount: u64, rate: u64) -> u64 {
+    if rate == 0 {
+        return 0;
+    }
+    ((amount as u128 * rate as u128 / DENOMINATOR_BPS as u128) as u64).max(1)
+}
+
 account!(OreAccount, Round);
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::consts::{ADMIN_FEE, INTERMISSION_SLOTS, PROTOCOL_FEE, ROUND_SLOTS};
 
     fn default_round(id: u64) -> Round {
         Round {
@@ -185,7 +246,70 @@ mod tests {
             total_returned_sol: 0,
             total_miners: 0,
             top_miner: Pubkey::default(),
+            admin_fee_rate: ADMIN_FEE,
+            protocol_fee_rate: PROTOCOL_FEE,
+            round_slots: ROUND_SLOTS,
+            intermission_slots: INTERMISSION_SLOTS,
+            mass_weighted: 0,
+        }
+    }
+
+    #[test]
+    fn test_square_fees_match_legacy_rates() {
+        let mut round = default_round(1);
+        for deployed in [1, 99, 100, 101, 12_345, 1_000_000_007] {
+            round.deployed[0] = deployed;
+            let legacy_admin = (deployed / 100).max(1);
+            let legacy_protocol = (deployed.saturating_sub(legacy_admin) / 10).max(1);
+            assert_eq!(round.square_fees(0, 1), (legacy_admin, legacy_protocol));
+            assert_eq!(round.square_fees(0, 0), (legacy_admin, 0));
+        }
+    }
+
+    #[test]
+    fn test_square_fees_use_round_rates() {
+        let mut round = default_round(1);
+        round.admin_fee_rate = 250;
+        round.protocol_fee_rate = 2_000;
+        round.deployed[0] = 1_000_000;
+        assert_eq!(round.square_fees(0, 1), (25_000, 195_000));
+        round.admin_fee_rate = 0;
+        round.protocol_fee_rate = 0;
+        assert_eq!(round.square_fees(0, 1), (0, 0));
+        assert_eq!(round.square_fees(1, 0), (0, 0));
+    }
+
+    #[test]
+    fn test_calculate_fees_sums_squares() {
+        let mut round = default_round(1);
+        round.deployed[0] = 1_000;
+        round.deployed[1] = 2_000;
+        round.deployed[2] = 3_000;
+        assert_eq!(round.calculate_fees(0), (60, 198 + 297));
+    }
+
+    #[test]
+    fn test_top_miner_sample_uses_sol_by_default() {
+        let mut round = default_round(1);
+        round.deployed[3] = 1_000;
+        round.mass[3] = 7;
+        assert!(!round.is_mass_weighted());
+        for rng in [0, 1, 12345, u64::MAX] {
+            assert_eq!(round.top_miner_sample(rng, 3), rng.reverse_bits() % 1_000);
+        }
+    }
+
+    #[test]
+    fn test_top_miner_sample_uses_mass_when_weighted() {
+        let mut round = default_round(1);
+        round.mass_weighted = 1;
+        round.deployed[3] = 1_000;
+        round.mass[3] = 7;
+        assert!(round.is_mass_weighted());
+        for rng in [0, 1, 12345, u64::MAX] {
+            assert_eq!(round.top_miner_sample(rng, 3), rng.reverse_bits() % 7);
         }
+        assert_eq!(round.top_miner_sample(12345, 4), 0);
     }
 
     #[test]
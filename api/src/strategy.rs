@@ -0,0 +1,445 @@
+use solana_program::keccak::hashv;
+use steel::*;
+
+use crate::consts::ONE_ORE;
+use crate::state::{Automation, Round};
+
+/// Versions of the square selection used by the Random automation strategy.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, IntoPrimitive, TryFromPrimitive)]
+pub enum StrategyVersion {
+    /// The original selection. The first deploy uses the raw mask, and the solo / split shuffle
+    /// draws from overlapping windows of a single seed.
+    V1 = 0,
+
+    /// Every deploy, including the first, is randomized, and each shuffle draw is hashed independently.
+    V2 = 1,
+}
+
+impl StrategyVersion {
+    /// Unknown versions fall back to V1, which is what automations created before versioning use.
+    pub fn from_u64(value: u64) -> Self {
+        Self::try_from(value as u8).unwrap_or(Self::V1)
+    }
+}
+
+/// Returns the squares a Random automation deploys to in the given round.
+pub fn random_squares(automation: &Automation, round: &Round) -> [bool; 25] {
+    let version = StrategyVersion::from_u64(automation.conditions.strategy_version as u64);
+
+    // V1 uses the mask provided by the user on the first deploy.
+    if version == StrategyVersion::V1 && automation.total_sol_spent == 0 {
+        let mut squares = [false; 25];
+        for (i, square) in squares.iter_mut().enumerate() {
+            *square = (automation.mask & (1 << i)) != 0;
+        }
+        return squares;
+    }
+
+    // Select squares by the user's solo / split preference, if any.
+    let conditions = &automation.conditions;
+    if conditions.solo_tiles > 0 || conditions.split_tiles > 0 {
+        return solo_split_squares(
+            round.distribution_mask(),
+            conditions.solo_tiles as usize,
+            conditions.split_tiles as usize,
+            &solo_split_seed(&automation.authority, round.id),
+            version,
+        );
+    }
+
+    // Otherwise select as many squares as are set in the mask.
+    let num_squares = (automation.mask & 0x1FF_FFFF).count_ones() as u64;
+    random_mask(num_squares, &random_seed(&automation.authority, round.id))
+}
+
+/// Ways the ExpectedValue strategy ranks squares.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, IntoPrimitive, TryFromPrimitive)]
+pub enum ExpectedValueMode {
+    /// Deploy to the squares with the least SOL deployed, breaking ties by fewest miners.
+    LeastCrowded = 0,
+
+    /// Deploy to the squares with the best expected SOL return, skipping squares where it is not positive.
+    /// Until there is a production cost EMA to value ORE rewards, squares are ranked by their share of
+    /// the ORE weight and none are skipped.
+    BestReturn = 1,
+}
+
+impl ExpectedValueMode {
+    pub fn from_u64(value: u64) -> Self {
+        Self::try_from(value as u8).unwrap_or(Self::LeastCrowded)
+    }
+}
+
+/// Returns the squares an ExpectedValue automation deploys to in the given round.
+/// Up to `ev_squares` squares are chosen from the automation's mask, given the current state of the round.
+/// The mass is the weight each deploy would carry if the round is mass weighted.
+/// The production cost EMA (lamports per whole ORE) values ORE rewards, and the motherlode is in grams.
+pub fn expected_value_squares(
+    automation: &Automation,
+    round: &Round,
+    mass: u64,
+    production_cost_ema: u64,
+    motherlode: u64,
+) -> [bool; 25] {
+    let mode = ExpectedValueMode::from_u64(automation.conditions.ev_mode as u64);
+    let amount = automation.amount;
+
+    // Collect the eligible squares and their expected returns.
+    let mut idxs = [0usize; 25];
+    let mut returns = [0i64; 25];
+    let mut num_eligible = 0;
+    for i in (0..25).filter(|&i| (automation.mask & (1 << i)) != 0) {
+        if mode == ExpectedValueMode::BestReturn {
+            returns[i] = expected_return(round, i, amount, mass, production_cost_ema, motherlode);
+
+            // Skip squares with negative expected returns, once ORE rewards can be valued.
+            if production_cost_ema > 0 && returns[i] <= 0 {
+                continue;
+            }
+        }
+        idxs[num_eligible] = i;
+        num_eligible += 1;
+    }
+
+    let idxs = &mut idxs[..num_eligible];
+    match mode {
+        // Rank squares by SOL deployed, then by number of miners.
+        ExpectedValueMode::LeastCrowded => {
+            idxs.sort_unstable_by_key(|&i| (round.deployed[i], round.count[i], i));
+        }
+
+        // Rank squares by expected return, or by ORE weight if ORE rewards cannot be valued.
+        ExpectedValueMode::BestReturn => {
+            if production_cost_ema > 0 {
+                idxs.sort_unstable_by_key(|&i| (std::cmp::Reverse(returns[i]), i));
+            } else {
+                idxs.sort_unstable_by_key(|&i| (round.weights()[i], i));
+            }
+        }
+    }
+
+    let mut squares = [false; 25];
+    for &i in idxs.iter().take(automation.conditions.ev_squares as usize) {
+        squares[i] = true;
+    }
+    squares
+}
+
+/// Returns the expected net SOL return (lamports) of deploying `amount` with the given mass on a square.
+/// This mirrors the reward calculation at checkpoint. Each square returns its SOL to the miners on it after fees,
+/// where the protocol fee is only charged if the square loses, which it does with 24 in 25 odds.
+/// The winning square also pays ORE by weight, which is valued at the production cost.
+pub fn expected_return(
+    round: &Round,
+    square: usize,
+    amount: u64,
+    mass: u64,
+    production_cost_ema: u64,
+    motherlode: u64,
+) -> i64 {
+    if amount == 0 {
+        return 0;
+    }
+
+    // Expected SOL returned, as a share of the square after fees.
+    let square_total = round.deployed[square] + amount;
+    let (admin_fee, protocol_fee) = round.deploy_fees(square_total);
+    let square_returned = square_total as u128 - admin_fee as u128 - protocol_fee as u128 * 24 / 25;
+    let expected_sol = square_returned * amount as u128 / square_total as u128;
+
+    // Expected ORE, as a share by weight of the winning square's +1 ORE and 1 in 500 motherlode.
+    let (weight, square_weight) = if round.is_mass_weighted() {
+        (mass, round.mass[square] + mass)
+    } else {
+        (amount, square_total)
+    };
+    let expected_ore = (ONE_ORE as u128 + motherlode as u128 / 500) * weight as u128
+        / square_weight.max(1) as u128
+        / 25;
+    let expected_ore_value = expected_ore * production_cost_ema as u128 / ONE_ORE as u128;
+
+    (expected_sol as i128 + expected_ore_value as i128 - amount as i128) as i64
+}
+
+/// Returns the seed for selecting an automation's squares in a round.
+pub fn random_seed(authority: &Pubkey, round_id: u64) -> [u8; 32] {
+    hashv(&[&authority.to_bytes(), &round_id.to_le_bytes()]).0
+}
+
+/// Returns the seed for shuffling an automation's solo and split squares in a round.
+pub fn solo_split_seed(authority: &Pubkey, round_id: u64) -> [u8; 32] {
+    hashv(&[
+        &authority.to_bytes(),
+        &round_id.to_le_bytes(),
+        b"solo_split_mask",
+    ])
+    .0
+}
+
+/// Selects `num_squares` of the 25 squares, using one byte of the seed per square.
+pub fn random_mask(num_squares: u64, seed: &[u8; 32]) -> [bool; 25] {
+    let mut mask = [false; 25];
+    let mut selected = 0;
+    for (i, square) in mask.iter_mut().enumerate() {
+        let remaining_needed = num_squares.saturating_sub(selected);
+        let remaining_positions = 25 - i as u64;
+        if remaining_needed > 0 && (seed[i] as u64) * remaining_positions < remaining_needed * 256 {
+            *square = true;
+            selected += 1;
+        }
+    }
+    mask
+}
+
+/// Selects up to `solo_tiles` squares whose rewards are not split and up to `split_tiles` squares whose rewards are,
+/// as given by the round's distribution mask.
+pub fn solo_split_squares(
+    distribution_mask: u32,
+    solo_tiles: usize,
+    split_tiles: usize,
+    seed: &[u8; 32],
+    version: StrategyVersion,
+) -> [bool; 25] {
+    // Collect the indices of solo and split squares.
+    let mut solo_idxs = [0usize; 25];
+    let mut split_idxs = [0usize; 25];
+    let mut num_solo = 0;
+    let mut num_split = 0;
+    for i in 0..25 {
+        if (distribution_mask & (1 << i)) != 0 {
+            solo_idxs[num_solo] = i;
+            num_solo += 1;
+        } else {
+            split_idxs[num_split] = i;
+            num_split += 1;
+        }
+    }
+
+    // Shuffle both index sets with the same seed.
+    shuffle_squares(&mut solo_idxs[..num_solo], seed, version);
+    shuffle_squares(&mut split_idxs[..num_split], seed, version);
+
+    // Take the preferred number of each.
+    let mut squares = [false; 25];
+    for &i in &solo_idxs[..num_solo.min(solo_tiles)] {
+        squares[i] = true;
+    }
+    for &i in &split_idxs[..num_split.min(split_tiles)] {
+        squares[i] = true;
+    }
+    squares
+}
+
+/// Shuffles square indices in place with a Fisher-Yates shuffle driven by the seed.
+pub fn shuffle_squares(idxs: &mut [usize], seed: &[u8; 32], version: StrategyVersion) {
+    for i in (1..idxs.len()).rev() {
+        let r = match version {
+            // Reads an 8-byte window of the seed that starts at `i`, so adjacent draws share 7 bytes.
+            StrategyVersion::V1 => {
+                let start = i % (32 - 8 + 1);
+                u64::from_le_bytes(seed[start..start + 8].try_into().unwrap())
+            }
+            StrategyVersion::V2 => {
+                let h = hashv(&[seed, &(i as u64).to_le_bytes()]).0;
+                u64::from_le_bytes(h[0..8].try_into().unwrap())
+            }
+        };
+        let j = (r % (i as u64 + 1)) as usize;
+        idxs.swap(i, j);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn selected(squares: &[bool; 25]) -> Vec<usize> {
+        (0..25).filter(|&i| squares[i]).collect()
+    }
+
+    #[test]
+    fn test_random_mask_selects_exact_count() {
+        let seed = random_seed(&Pubkey::new_from_array([7; 32]), 42);
+        for n in 0..=25 {
+            let mask = random_mask(n, &seed);
+            assert_eq!(mask.iter().filter(|&&s| s).count() as u64, n);
+        }
+    }
+
+    #[test]
+    fn test_random_mask_pinned() {
+        let seed = random_seed(&Pubkey::new_from_array([7; 32]), 42);
+        assert_eq!(selected(&random_mask(5, &seed)), vec![9, 12, 14, 15, 21]);
+    }
+
+    #[test]
+    fn test_shuffle_pinned() {
+        let seed = solo_split_seed(&Pubkey::new_from_array([7; 32]), 42);
+        let mut v1: Vec<usize> = (0..10).collect();
+        let mut v2: Vec<usize> = (0..10).collect();
+        shuffle_squares(&mut v1, &seed, StrategyVersion::V1);
+        shuffle_squares(&mut v2, &seed, StrategyVersion::V2);
+        assert_eq!(v1, vec![2, 9, 4, 0, 3, 5, 7, 6, 8, 1]);
+        assert_eq!(v2, vec![9, 5, 8, 6, 2, 4, 7, 0, 3, 1]);
+    }
+
+    #[test]
+    fn test_solo_split_squares_respects_distribution() {
+        let distribution_mask = 0b1111111111; // Squares 0-9 are solo.
+        let seed = solo_split_seed(&Pubkey::new_from_array([7; 32]), 42);
+        for version in [StrategyVersion::V1, StrategyVersion::V2] {
+            let squares = solo_split_squares(distribution_mask, 2, 3, &seed, version);
+            let picked = selected(&squares);
+            assert_eq!(picked.iter().filter(|&&i| i < 10).count(), 2);
+            assert_eq!(picked.iter().filter(|&&i| i >= 10).count(), 3);
+        }
+
+        // Preferences larger than the available squares are capped.
+        let squares = solo_split_squares(distribution_mask, 25, 0, &seed, StrategyVersion::V2);
+        assert_eq!(selected(&squares), (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_random_squares_first_deploy() {
+        let mut automation = Automation::zeroed();
+        automation.authority = Pubkey::new_from_array([7; 32]);
+        automation.mask = 0b111;
+        let mut round = Round::zeroed();
+        round.id = 42;
+
+        // V1 deploys to the raw mask on the first deploy.
+        assert_eq!(
+            selected(&random_squares(&automation, &round)),
+            vec![0, 1, 2]
+        );
+
+        // V2 randomizes the first deploy like any other.
+        automation.conditions.strategy_version = StrategyVersion::V2 as u16;
+        let squares = random_squares(&automation, &round);
+        assert_eq!(
+            squares,
+            random_mask(3, &random_seed(&automation.authority, round.id))
+        );
+
+        // V1 randomizes once the automation has deployed.
+        automation.conditions.strategy_version = StrategyVersion::V1 as u16;
+        automation.total_sol_spent = 1;
+        assert_eq!(random_squares(&automation, &round), squares);
+    }
+
+    #[test]
+    fn test_expected_value_least_crowded() {
+        let mut automation = Automation::zeroed();
+        automation.amount = 1_000;
+        automation.mask = 0b1_1111; // Squares 0-4 are eligible.
+        automation.conditions.ev_squares = 2;
+        let mut round = Round::zeroed();
+        round.deployed = [500; 25];
+        round.deployed[1] = 100;
+        round.deployed[3] = 200;
+        round.deployed[4] = 200;
+        round.deployed[10] = 0; // Not in the mask.
+        round.count[3] = 2;
+        round.count[4] = 1;
+
+        // Ties on deployed SOL are broken by the number of miners.
+        let squares = expected_value_squares(&automation, &round, 0, 0, 0);
+        assert_eq!(selected(&squares), vec![1, 4]);
+
+        // Selection is capped by the number of eligible squares.
+        automation.conditions.ev_squares = 25;
+        let squares = expected_value_squares(&automation, &round, 0, 0, 0);
+        assert_eq!(selected(&squares), vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_expected_value_best_return_skips_negative_squares() {
+        let mut automation = Automation::zeroed();
+        automation.amount = 1_000_000;
+        automation.mask = 0b111;
+        automation.conditions.ev_squares = 3;
+        automation.conditions.ev_mode = ExpectedValueMode::BestReturn as u16;
+        let mut round = Round::zeroed();
+        round.admin_fee_rate = 100;
+        round.protocol_fee_rate = 1_000;
+        round.deployed[0] = 0;
+        round.deployed[1] = 1_000_000;
+        round.deployed[2] = 1_000_000_000;
+
+        // Valued at 1 SOL per ORE, only the emptier squares are worth deploying to.
+        let price = 1_000_000_000;
+        assert!(expected_return(&round, 0, automation.amount, 0, price, 0) > 0);
+        assert!(expected_return(&round, 1, automation.amount, 0, price, 0) > 0);
+        assert!(expected_return(&round, 2, automation.amount, 0, price, 0) < 0);
+        let squares = expected_value_squares(&automation, &round, 0, price, 0);
+        assert_eq!(selected(&squares), vec![0, 1]);
+
+        // A larger motherlode raises expected returns.
+        assert!(
+            expected_return(&round, 2, automation.amount, 0, price, 10_000 * ONE_ORE)
+                > expected_return(&round, 2, automation.amount, 0, price, 0)
+        );
+
+        // Without a production cost to value ORE, no squares are skipped.
+        let squares = expected_value_squares(&automation, &round, 0, 0, 0);
+        assert_eq!(selected(&squares), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_expected_value_best_return_ranks_by_return() {
+        let mut automation = Automation::zeroed();
+        automation.amount = 1_000_000;
+        automation.mask = 0b11;
+        automation.conditions.ev_squares = 1;
+        let mut round = Round::zeroed();
+        round.admin_fee_rate = 100;
+        round.protocol_fee_rate = 1_000;
+        round.mass_weighted = 1;
+
+        // Square 0 has the least SOL, but it was deployed early and carries far more mass than square 1.
+        round.deployed[0] = 1_000_000;
+        round.mass[0] = 1_000_000;
+        round.deployed[1] = 2_000_000;
+        round.mass[1] = 100_000;
+        let mass = 100_000;
+        let price = 1_000_000_000;
+        assert!(
+            expected_return(&round, 1, automation.amount, mass, price, 0)
+                > expected_return(&round, 0, automation.amount, mass, price, 0)
+        );
+
+        // The least crowded square is not the best returning one.
+        let squares = expected_value_squares(&automation, &round, mass, price, 0);
+        assert_eq!(selected(&squares), vec![0]);
+        automation.conditions.ev_mode = ExpectedValueMode::BestReturn as u16;
+        let squares = expected_value_squares(&automation, &round, mass, price, 0);
+        assert_eq!(selected(&squares), vec![1]);
+
+        // Without a production cost, squares are ranked by their ORE weight.
+        let squares = expected_value_squares(&automation, &round, mass, 0, 0);
+        assert_eq!(selected(&squares), vec![1]);
+    }
+
+    #[test]
+    fn test_expected_return_mirrors_fees() {
+        let mut round = Round::zeroed();
+        round.admin_fee_rate = 100;
+        round.protocol_fee_rate = 1_000;
+
+        // Without ORE value, the expected return is the admin fee and the protocol fee if the square loses.
+        let amount = 1_000_000;
+        let (admin_fee, protocol_fee) = round.deploy_fees(amount);
+        let expected = -((admin_fee + protocol_fee * 24 / 25) as i64);
+        assert_eq!(expected_return(&round, 0, amount, 0, 0, 0), expected);
+        assert_eq!(expected_return(&round, 0, 0, 0, 0, 0), 0);
+    }
+
+    #[test]
+    fn test_strategy_version_from_u64() {
+        assert_eq!(StrategyVersion::from_u64(0), StrategyVersion::V1);
+        assert_eq!(StrategyVersion::from_u64(1), StrategyVersion::V2);
+        assert_eq!(StrategyVersion::from_u64(u64::MAX), StrategyVersion::V1);
+    }
+}
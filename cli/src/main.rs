@@ -50,6 +50,9 @@ async fn main() {
         "automation" => {
             log_automation(&rpc).await.unwrap();
         }
+        "executors" => {
+            log_executors(&rpc).await.unwrap();
+        }
         "clock" => {
             log_clock(&rpc).await.unwrap();
         }
@@ -86,6 +89,54 @@ async fn main() {
         "set_admin" => {
             set_admin(&rpc, &payer).await.unwrap();
         }
+        "accept_admin" => {
+            accept_admin(&rpc, &payer).await.unwrap();
+        }
+        "set_fee_collector" => {
+            set_fee_collector(&rpc, &payer).await.unwrap();
+        }
+        "set_fee_rate" => {
+            set_fee_rate(&rpc, &payer).await.unwrap();
+        }
+        "set_admin_fee" => {
+            set_admin_fee(&rpc, &payer).await.unwrap();
+        }
+        "set_round_timing" => {
+            set_round_timing(&rpc, &payer).await.unwrap();
+        }
+        "set_var" => {
+            set_var(&rpc, &payer).await.unwrap();
+        }
+        "set_withdraw_config" => {
+            set_withdraw_config(&rpc, &payer).await.unwrap();
+        }
+        "set_mass_round" => {
+            set_mass_round(&rpc, &payer).await.unwrap();
+        }
+        "withdraw" => {
+            withdraw(&rpc, &payer).await.unwrap();
+        }
+        "set_miner_preferences" => {
+            set_miner_preferences(&rpc, &payer).await.unwrap();
+        }
+        "claim_and_stake" => {
+            claim_and_stake(&rpc, &payer).await.unwrap();
+        }
+        "claim_and_bury" => {
+            claim_and_bury(&rpc, &payer).await.unwrap();
+        }
+        "withdraw_automation" => {
+            withdraw_automation(&rpc, &payer).await.unwrap();
+        }
+        "register_executor" => {
+            register_executor(&rpc, &payer).await.unwrap();
+        }
+        "close_miner" => {
+            close_miner(&rpc, &payer).await.unwrap();
+        }
+        "migrate_miner" => {
+            migrate_miner(&rpc, &payer).await.unwrap();
+        }
         "ata" => {
             ata(&rpc, &payer).await.unwrap();
         }
@@ -244,10 +295,11 @@ async fn new_var(
     let commit = keccak::Hash::from_str(&commit).expect("Invalid COMMIT");
     let samples = std::env::var("SAMPLES").expect("Missing SAMPLES env var");
     let samples = u64::from_str(&samples).expect("Invalid SAMPLES");
+    let id = std::env::var("ID").map_or(0, |id| u64::from_str(&id).expect("Invalid ID"));
     let board_address = board_pda().0;
-    let var_address = entropy_api::state::var_pda(board_address, 0).0;
+    let var_address = entropy_api::state::var_pda(board_address, id).0;
     println!("Var address: {}", var_address);
-    let ix = ore_api::sdk::new_var(payer.pubkey(), provider, 0, commit.to_bytes(), samples);
+    let ix = ore_api::sdk::new_var(payer.pubkey(), provider, id, commit.to_bytes(), samples);
     submit_transaction(rpc, payer, &[ix]).await?;
     Ok(())
 }
@@ -301,30 +353,37 @@ async fn claim(
     rpc: &RpcClient,
     payer: &solana_sdk::signer::keypair::Keypair,
 ) -> Result<(), anyhow::Error> {
-    // Calculate rewards.
-    let recipient = std::env::var("RECIPIENT");
-    let authority = payer.pubkey();
-    let treasury: Treasury = get_treasury(&rpc).await?;
-    let mut miner = get_miner(&rpc, authority).await?;
-    miner.update_rewards(&treasury);
+    // Claim on behalf of another authority if the payer is their claim delegate.
+    let authority = std::env::var("AUTHORITY").unwrap_or(payer.pubkey().to_string());
+    let authority = Pubkey::from_str(&authority).expect("Invalid AUTHORITY");
+    let miner = get_miner(&rpc, authority).await?;
+
+    // Delegated claims must go to the pre-approved destination.
+    let recipient = match std::env::var("RECIPIENT") {
+        Ok(recipient) => Pubkey::from_str(&recipient).expect("Invalid RECIPIENT"),
+        Err(_) if authority == payer.pubkey() => payer.pubkey(),
+        Err(_) => miner.claim_destination,
+    };
 
     // Claim rewards.
     let mut ixs = vec![];
-    ixs.push(ore_api::sdk::claim_sol(payer.pubkey()));
-    ixs.push(ore_api::sdk::claim_ore(payer.pubkey(), DENOMINATOR_BPS));
-
-    // Transfer rewards to miner.
-    if let Ok(recipient) = recipient {
-        if let Ok(recipient) = Pubkey::from_str(&recipient) {
-            ixs.push(spl_token::instruction::transfer(
-                &spl_token::ID,
-                &authority,
-                &recipient,
-                &authority,
-                &[&authority],
-                miner.rewards_ore + miner.refined_ore,
-            )?);
-        }
+    if authority == payer.pubkey() && recipient == payer.pubkey() {
+        ixs.push(ore_api::sdk::claim_sol(payer.pubkey()));
+        ixs.push(ore_api::sdk::claim_ore(payer.pubkey(), DENOMINATOR_BPS));
+    } else {
+        let recipient_tokens = get_associated_token_address(&recipient, &MINT_ADDRESS);
+        ixs.push(ore_api::sdk::claim_sol_v2(
+            payer.pubkey(),
+            authority,
+            recipient,
+        ));
+        ixs.push(ore_api::sdk::claim_ore_v2(
+            payer.pubkey(),
+            authority,
+            recipient,
+            recipient_tokens,
+            DENOMINATOR_BPS,
+        ));
     }
 
     // Submit transaction.
@@ -332,6 +391,24 @@ async fn claim(
     Ok(())
 }
 
+async fn claim_and_stake(
+    rpc: &RpcClient,
+    payer: &solana_sdk::signer::keypair::Keypair,
+) -> Result<(), anyhow::Error> {
+    let ix = ore_api::sdk::claim_and_stake(payer.pubkey(), DENOMINATOR_BPS, 0, 0);
+    submit_transaction(rpc, payer, &[ix]).await?;
+    Ok(())
+}
+
+async fn claim_and_bury(
+    rpc: &RpcClient,
+    payer: &solana_sdk::signer::keypair::Keypair,
+) -> Result<(), anyhow::Error> {
+    let ix = ore_api::sdk::claim_and_bury(payer.pubkey(), DENOMINATOR_BPS);
+    submit_transaction(rpc, payer, &[ix]).await?;
+    Ok(())
+}
+
 async fn buyback(
     rpc: &RpcClient,
     payer: &solana_sdk::signer::keypair::Keypair,
@@ -431,14 +508,14 @@ pub async fn get_address_lookup_table_accounts(
     Ok(accounts)
 }
 
-pub const ORE_VAR_ADDRESS: Pubkey = pubkey!("BWCaDY96Xe4WkFq1M7UiCCRcChsJ3p51L5KrGzhxgm2E");
-
 async fn reset(
     rpc: &RpcClient,
     payer: &solana_sdk::signer::keypair::Keypair,
 ) -> Result<(), anyhow::Error> {
     let board = get_board(rpc).await?;
-    let mut var = get_var(rpc, ORE_VAR_ADDRESS).await?;
+    let config = get_config(rpc).await?;
+    let var_address = config.entropy_var();
+    let mut var = get_var(rpc, var_address).await?;
 
     let hash = solana_program::keccak::hashv(&[&var.end_at.to_le_bytes()]);
     var.slot_hash = hash.to_bytes();
@@ -448,7 +525,7 @@ async fn reset(
     println!("Var: {:?}", var);
 
     let client = reqwest::Client::new();
-    let url = format!("https://entropy-api.onrender.com/var/{ORE_VAR_ADDRESS}/seed");
+    let url = format!("https://entropy-api.onrender.com/var/{var_address}/seed");
     let response = client
         .get(url)
         .send()
@@ -463,14 +540,15 @@ async fn reset(
     let top_miner = calculate_top_miner(rpc, &mut var, response.seed, board.round_id).await?;
     println!("Top miner: {}", top_miner);
 
-    let config = get_config(rpc).await?;
-    let sample_ix = entropy_api::sdk::sample(payer.pubkey(), ORE_VAR_ADDRESS);
-    let reveal_ix = entropy_api::sdk::reveal(payer.pubkey(), ORE_VAR_ADDRESS, response.seed);
+    let sample_ix = entropy_api::sdk::sample(payer.pubkey(), var_address);
+    let reveal_ix = entropy_api::sdk::reveal(payer.pubkey(), var_address, response.seed);
     let reset_ix = ore_api::sdk::reset(
         payer.pubkey(),
-        ADMIN_FEE_COLLECTOR,
+        config.fee_collector(),
         board.round_id,
         top_miner,
+        var_address,
+        config.entropy_program(),
     );
     let sig = submit_transaction(rpc, payer, &[sample_ix, reveal_ix, reset_ix]).await?;
     println!("Reset: {}", sig);
@@ -513,8 +591,11 @@ async fn calculate_top_miner(
     let top_miner_sample = round.top_miner_sample(r, winning_square);
 
     println!(
-        "Round {} not split. winning_square={}, top_miner_sample={}",
-        round_id, winning_square, top_miner_sample
+        "Round {} not split. winning_square={}, top_miner_sample={}, mass_weighted={}",
+        round_id,
+        winning_square,
+        top_miner_sample,
+        round.is_mass_weighted()
     );
 
     // Fetch all miners for this round (round_id is at offset 512 in Miner account)
@@ -525,13 +606,13 @@ async fn calculate_top_miner(
 
     // Linear search for matching miner
     for (miner_pda, miner) in miners.iter() {
-        let cumulative = miner.cumulative[winning_square];
-        let deployed = miner.deployed[winning_square];
-
-        if top_miner_sample >= cumulative && top_miner_sample < cumulative + deployed {
+        if miner.is_top_miner(&round, top_miner_sample, winning_square) {
             println!(
-                "Found top miner: {} (cumulative={}, deployed={})",
-                miner_pda, cumulative, deployed
+                "Found top miner: {} (cumulative={}, deployed={}, mass={})",
+                miner_pda,
+                miner.cumulative[winning_square],
+                miner.deployed[winning_square],
+                miner.mass[winning_square]
             );
             return Ok(miner.authority);
         }
@@ -554,6 +635,7 @@ async fn deploy(
     let square_id = std::env::var("SQUARE").expect("Missing SQUARE env var");
     let square_id = u64::from_str(&square_id).expect("Invalid SQUARE");
     let board = get_board(rpc).await?;
+    let config = get_config(rpc).await?;
     let mut squares = [false; 25];
     squares[square_id as usize] = true;
     let ix = ore_api::sdk::deploy(
@@ -562,6 +644,8 @@ async fn deploy(
         amount,
         board.round_id,
         squares,
+        config.entropy_var(),
+        config.entropy_program(),
     );
     submit_transaction(rpc, payer, &[ix]).await?;
     Ok(())
@@ -574,6 +658,7 @@ async fn deploy_all(
     let amount = std::env::var("AMOUNT").expect("Missing AMOUNT env var");
     let amount = u64::from_str(&amount).expect("Invalid AMOUNT");
     let board = get_board(rpc).await?;
+    let config = get_config(rpc).await?;
     let squares = [true; 25];
     let ix = ore_api::sdk::deploy(
         payer.pubkey(),
@@ -581,6 +666,8 @@ async fn deploy_all(
         board.round_id,
         amount,
         squares,
+        config.entropy_var(),
+        config.entropy_program(),
     );
     submit_transaction(rpc, payer, &[ix]).await?;
     Ok(())
@@ -590,11 +677,238 @@ async fn set_admin(
     rpc: &RpcClient,
     payer: &solana_sdk::signer::keypair::Keypair,
 ) -> Result<(), anyhow::Error> {
-    let ix = ore_api::sdk::set_admin(payer.pubkey(), payer.pubkey());
+    let admin = std::env::var("ADMIN").expect("Missing ADMIN env var");
+    let admin = Pubkey::from_str(&admin).expect("Invalid ADMIN");
+    let ix = ore_api::sdk::set_admin(payer.pubkey(), admin);
+    submit_transaction(rpc, payer, &[ix]).await?;
+    println!("Proposed admin: {}", admin);
+    Ok(())
+}
+
+async fn accept_admin(
+    rpc: &RpcClient,
+    payer: &solana_sdk::signer::keypair::Keypair,
+) -> Result<(), anyhow::Error> {
+    let config = get_config(rpc).await?;
+    if config.pending_admin != payer.pubkey() {
+        return Err(anyhow::anyhow!(
+            "Pending admin is {}, not {}",
+            config.pending_admin,
+            payer.pubkey()
+        ));
+    }
+    let ix = ore_api::sdk::accept_admin(payer.pubkey());
+    submit_transaction(rpc, payer, &[ix]).await?;
+    Ok(())
+}
+
+async fn set_fee_collector(
+    rpc: &RpcClient,
+    payer: &solana_sdk::signer::keypair::Keypair,
+) -> Result<(), anyhow::Error> {
+    let fee_collector = std::env::var("FEE_COLLECTOR").expect("Missing FEE_COLLECTOR env var");
+    let fee_collector = Pubkey::from_str(&fee_collector).expect("Invalid FEE_COLLECTOR");
+    let ix = ore_api::sdk::set_fee_collector(payer.pubkey(), fee_collector);
     submit_transaction(rpc, payer, &[ix]).await?;
     Ok(())
 }
 
+async fn set_fee_rate(
+    rpc: &RpcClient,
+    payer: &solana_sdk::signer::keypair::Keypair,
+) -> Result<(), anyhow::Error> {
+    let fee_rate = std::env::var("FEE_RATE").expect("Missing FEE_RATE env var");
+    let fee_rate = u64::from_str(&fee_rate).expect("Invalid FEE_RATE");
+    let ix = ore_api::sdk::set_fee_rate(payer.pubkey(), fee_rate);
+    submit_transaction(rpc, payer, &[ix]).await?;
+    Ok(())
+}
+
+async fn set_admin_fee(
+    rpc: &RpcClient,
+    payer: &solana_sdk::signer::keypair::Keypair,
+) -> Result<(), anyhow::Error> {
+    let admin_fee = std::env::var("ADMIN_FEE").expect("Missing ADMIN_FEE env var");
+    let admin_fee = u64::from_str(&admin_fee).expect("Invalid ADMIN_FEE");
+    let ix = ore_api::sdk::set_admin_fee(payer.pubkey(), admin_fee);
+    submit_transaction(rpc, payer, &[ix]).await?;
+    Ok(())
+}
+
+async fn set_round_timing(
+    rpc: &RpcClient,
+    payer: &solana_sdk::signer::keypair::Keypair,
+) -> Result<(), anyhow::Error> {
+    let round_slots = std::env::var("ROUND_SLOTS").expect("Missing ROUND_SLOTS env var");
+    let round_slots = u64::from_str(&round_slots).expect("Invalid ROUND_SLOTS");
+    let intermission_slots =
+        std::env::var("INTERMISSION_SLOTS").expect("Missing INTERMISSION_SLOTS env var");
+    let intermission_slots =
+        u64::from_str(&intermission_slots).expect("Invalid INTERMISSION_SLOTS");
+    let ix = ore_api::sdk::set_round_timing(payer.pubkey(), round_slots, intermission_slots);
+    submit_transaction(rpc, payer, &[ix]).await?;
+    Ok(())
+}
+
+async fn set_var(
+    rpc: &RpcClient,
+    payer: &solana_sdk::signer::keypair::Keypair,
+) -> Result<(), anyhow::Error> {
+    let id = std::env::var("ID").expect("Missing ID env var");
+    let id = u64::from_str(&id).expect("Invalid ID");
+    let board_address = board_pda().0;
+    let var_address = entropy_api::state::var_pda(board_address, id).0;
+    println!("Var address: {}", var_address);
+    let ix = ore_api::sdk::set_var(payer.pubkey(), var_address, entropy_api::ID);
+    submit_transaction(rpc, payer, &[ix]).await?;
+    Ok(())
+}
+
+async fn set_withdraw_config(
+    rpc: &RpcClient,
+    payer: &solana_sdk::signer::keypair::Keypair,
+) -> Result<(), anyhow::Error> {
+    let cutoff_slots = std::env::var("CUTOFF_SLOTS").expect("Missing CUTOFF_SLOTS env var");
+    let cutoff_slots = u64::from_str(&cutoff_slots).expect("Invalid CUTOFF_SLOTS");
+    let penalty_rate = std::env::var("PENALTY_RATE").expect("Missing PENALTY_RATE env var");
+    let penalty_rate = u64::from_str(&penalty_rate).expect("Invalid PENALTY_RATE");
+    let ix = ore_api::sdk::set_withdraw_config(payer.pubkey(), cutoff_slots, penalty_rate);
+    submit_transaction(rpc, payer, &[ix]).await?;
+    Ok(())
+}
+
+async fn set_mass_round(
+    rpc: &RpcClient,
+    payer: &solana_sdk::signer::keypair::Keypair,
+) -> Result<(), anyhow::Error> {
+    let mass_round_id = std::env::var("MASS_ROUND_ID").expect("Missing MASS_ROUND_ID env var");
+    let mass_round_id = u64::from_str(&mass_round_id).expect("Invalid MASS_ROUND_ID");
+    let ix = ore_api::sdk::set_mass_round(payer.pubkey(), mass_round_id);
+    submit_transaction(rpc, payer, &[ix]).await?;
+    Ok(())
+}
+
+async fn withdraw(
+    rpc: &RpcClient,
+    payer: &solana_sdk::signer::keypair::Keypair,
+) -> Result<(), anyhow::Error> {
+    let square_id = std::env::var("SQUARE").expect("Missing SQUARE env var");
+    let square_id = u64::from_str(&square_id).expect("Invalid SQUARE");
+    let amount = std::env::var("AMOUNT").map_or(u64::MAX, |amount| {
+        u64::from_str(&amount).expect("Invalid AMOUNT")
+    });
+    let board = get_board(rpc).await?;
+    let miner = get_miner(rpc, payer.pubkey()).await?;
+    let ix = ore_api::sdk::withdraw(
+        payer.pubkey(),
+        miner.automation_account_id(),
+        board.round_id,
+        square_id,
+        amount,
+    );
+    submit_transaction(rpc, payer, &[ix]).await?;
+    Ok(())
+}
+
+async fn set_miner_preferences(
+    rpc: &RpcClient,
+    payer: &solana_sdk::signer::keypair::Keypair,
+) -> Result<(), anyhow::Error> {
+    let auto_return = std::env::var("AUTO_RETURN").expect("Missing AUTO_RETURN env var");
+    let auto_return = bool::from_str(&auto_return).expect("Invalid AUTO_RETURN");
+    let ix = match std::env::var("CLAIM_DELEGATE") {
+        Ok(claim_delegate) => {
+            let claim_delegate = Pubkey::from_str(&claim_delegate).expect("Invalid CLAIM_DELEGATE");
+            let claim_destination =
+                std::env::var("CLAIM_DESTINATION").expect("Missing CLAIM_DESTINATION env var");
+            let claim_destination =
+                Pubkey::from_str(&claim_destination).expect("Invalid CLAIM_DESTINATION");
+            ore_api::sdk::set_miner_preferences_v2(
+                payer.pubkey(),
+                auto_return,
+                claim_delegate,
+                claim_destination,
+            )
+        }
+        Err(_) => ore_api::sdk::set_miner_preferences(payer.pubkey(), auto_return),
+    };
+    submit_transaction(rpc, payer, &[ix]).await?;
+    Ok(())
+}
+
+async fn withdraw_automation(
+    rpc: &RpcClient,
+    payer: &solana_sdk::signer::keypair::Keypair,
+) -> Result<(), anyhow::Error> {
+    let amount = std::env::var("AMOUNT").expect("Missing AMOUNT env var");
+    let amount = u64::from_str(&amount).expect("Invalid AMOUNT");
+    let id = std::env::var("AUTOMATION_ID").unwrap_or("0".to_string());
+    let id = u64::from_str(&id).expect("Invalid AUTOMATION_ID");
+    let ix = ore_api::sdk::withdraw_automation(payer.pubkey(), id, amount);
+    submit_transaction(rpc, payer, &[ix]).await?;
+    Ok(())
+}
+
+async fn register_executor(
+    rpc: &RpcClient,
+    payer: &solana_sdk::signer::keypair::Keypair,
+) -> Result<(), anyhow::Error> {
+    let name = std::env::var("NAME").expect("Missing NAME env var");
+    let max_fee = std::env::var("MAX_FEE").expect("Missing MAX_FEE env var");
+    let max_fee = u64::from_str(&max_fee).expect("Invalid MAX_FEE");
+    let max_bps = std::env::var("MAX_BPS").unwrap_or("0".to_string());
+    let max_bps = u64::from_str(&max_bps).expect("Invalid MAX_BPS");
+    let strategies = std::env::var("STRATEGIES").expect("Missing STRATEGIES env var");
+    let strategies = u8::from_str(&strategies).expect("Invalid STRATEGIES");
+    let paused = std::env::var("PAUSED").is_ok_and(|v| v == "true");
+    let uri = std::env::var("URI").unwrap_or_default();
+    assert!(uri.len() <= 64, "URI must be at most 64 bytes");
+    let mut uri_bytes = [0u8; 64];
+    uri_bytes[..uri.len()].copy_from_slice(uri.as_bytes());
+    let name_hash = keccak::hash(name.as_bytes()).to_bytes();
+    let ix = ore_api::sdk::register_executor(
+        payer.pubkey(),
+        name_hash,
+        max_fee,
+        max_bps,
+        strategies,
+        paused,
+        uri_bytes,
+    );
+    submit_transaction(rpc, payer, &[ix]).await?;
+    Ok(())
+}
+
+async fn close_miner(
+    rpc: &RpcClient,
+    payer: &solana_sdk::signer::keypair::Keypair,
+) -> Result<(), anyhow::Error> {
+    let ix = ore_api::sdk::close_miner(payer.pubkey());
+    submit_transaction(rpc, payer, &[ix]).await?;
+    Ok(())
+}
+
+async fn migrate_miner(
+    rpc: &RpcClient,
+    payer: &solana_sdk::signer::keypair::Keypair,
+) -> Result<(), anyhow::Error> {
+    let new_authority = read_keypair_file(
+        &std::env::var("NEW_AUTHORITY_KEYPAIR").expect("Missing NEW_AUTHORITY_KEYPAIR env var"),
+    )
+    .expect("Invalid NEW_AUTHORITY_KEYPAIR");
+    let ix = ore_api::sdk::migrate_miner(payer.pubkey(), new_authority.pubkey());
+    let blockhash = rpc.get_latest_blockhash().await?;
+    let transaction = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&payer.pubkey()),
+        &[payer, &new_authority],
+        blockhash,
+    );
+    let signature = rpc.send_and_confirm_transaction(&transaction).await?;
+    println!("Transaction submitted: {:?}", signature);
+    Ok(())
+}
+
 async fn checkpoint(
     rpc: &RpcClient,
     payer: &solana_sdk::signer::keypair::Keypair,
@@ -602,7 +916,12 @@ async fn checkpoint(
     let authority = std::env::var("AUTHORITY").unwrap_or(payer.pubkey().to_string());
     let authority = Pubkey::from_str(&authority).expect("Invalid AUTHORITY");
     let miner = get_miner(rpc, authority).await?;
-    let ix = ore_api::sdk::checkpoint(payer.pubkey(), authority, miner.round_id);
+    let ix = ore_api::sdk::checkpoint(
+        payer.pubkey(),
+        authority,
+        miner.automation_account_id(),
+        miner.round_id,
+    );
     submit_transaction(rpc, payer, &[ix]).await?;
     Ok(())
 }
@@ -614,7 +933,7 @@ async fn checkpoint_all(
     let clock = get_clock(rpc).await?;
     let miners = get_miners(rpc).await?;
     let mut expiry_slots = HashMap::new();
-    let mut ixs = vec![];
+    let mut targets: HashMap<u64, Vec<(Pubkey, u64)>> = HashMap::new();
     for (i, (_address, miner)) in miners.iter().enumerate() {
         if miner.checkpoint_id < miner.round_id {
             // Log the expiry slot for the round.
@@ -638,21 +957,19 @@ async fn checkpoint_all(
                     miner.authority,
                     (expires_at - clock.slot) as f64 * 0.4
                 );
-                ixs.push(ore_api::sdk::checkpoint(
-                    payer.pubkey(),
-                    miner.authority,
-                    miner.round_id,
-                ));
+                targets
+                    .entry(miner.round_id)
+                    .or_default()
+                    .push((miner.authority, miner.automation_account_id()));
             }
         }
     }
 
-    // Batch and submit the instructions.
-    while !ixs.is_empty() {
-        let batch = ixs
-            .drain(..std::cmp::min(10, ixs.len()))
-            .collect::<Vec<Instruction>>();
-        submit_transaction(rpc, payer, &batch).await?;
+    // Batch miners by round and submit one transaction per instruction.
+    for (round_id, round_miners) in &targets {
+        for ix in ore_api::sdk::checkpoint_many(payer.pubkey(), *round_id, round_miners) {
+            submit_transaction(rpc, payer, &[ix]).await?;
+        }
     }
 
     Ok(())
@@ -718,12 +1035,15 @@ async fn audit_curves(rpc: &RpcClient) -> Result<(), anyhow::Error> {
 async fn log_automation(rpc: &RpcClient) -> Result<(), anyhow::Error> {
     let authority = std::env::var("AUTHORITY").expect("Missing AUTHORITY env var");
     let authority = Pubkey::from_str(&authority).expect("Invalid AUTHORITY");
-    let address = automation_pda(authority).0;
-    let automation = get_automation(rpc, authority).await?;
+    let id = std::env::var("AUTOMATION_ID").unwrap_or("0".to_string());
+    let id = u64::from_str(&id).expect("Invalid AUTOMATION_ID");
+    let address = automation_pda_with_id(authority, id).0;
+    let automation = get_automation(rpc, authority, id).await?;
     let account_balance = rpc.get_balance(&address).await?;
     let required_rent = Rent::default().minimum_balance(Automation::SIZE);
     println!("Automation");
     println!("  address: {}", address);
+    println!("  id: {}", automation.id);
     println!("  amount: {} SOL", lamports_to_sol(automation.amount));
     println!("  required rent: {} SOL", lamports_to_sol(required_rent));
     println!("  authority: {}", automation.authority);
@@ -734,6 +1054,22 @@ async fn log_automation(rpc: &RpcClient) -> Result<(), anyhow::Error> {
     println!("  mask: {}", automation.mask);
     println!("  strategy: {}", automation.strategy);
     println!("  reload: {}", automation.reload);
+    println!("  expires at slot: {}", automation.limits.expires_at_slot);
+    println!("  expires at round: {}", automation.limits.expires_at_round);
+    println!(
+        "  rounds deployed: {} / {}",
+        automation.rounds_deployed, automation.limits.max_rounds
+    );
+    println!(
+        "  daily deployed: {} / {} SOL",
+        lamports_to_sol(automation.daily_deployed),
+        lamports_to_sol(automation.limits.max_daily_deploy)
+    );
+    println!(
+        "  stop-loss: {} SOL/ORE after {} rounds",
+        lamports_to_sol(automation.limits.stop_loss_production_cost),
+        automation.limits.stop_loss_min_rounds
+    );
     Ok(())
 }
 
@@ -742,6 +1078,7 @@ async fn log_automations(rpc: &RpcClient) -> Result<(), anyhow::Error> {
     for (i, (address, automation)) in automations.iter().enumerate() {
         println!("[{}/{}] {}", i + 1, automations.len(), address);
         println!("  authority: {}", automation.authority);
+        println!("  id: {}", automation.id);
         println!("  balance: {}", automation.balance);
         println!("  executor: {}", automation.executor);
         println!("  fee: {}", automation.fee);
@@ -752,6 +1089,21 @@ async fn log_automations(rpc: &RpcClient) -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+async fn log_executors(rpc: &RpcClient) -> Result<(), anyhow::Error> {
+    let executors = get_program_accounts::<Executor>(rpc, ore_api::ID, vec![]).await?;
+    for (i, (address, executor)) in executors.iter().enumerate() {
+        println!("[{}/{}] {}", i + 1, executors.len(), address);
+        println!("  authority: {}", executor.authority);
+        println!("  max fee: {} SOL", lamports_to_sol(executor.max_fee));
+        println!("  max bps: {}", executor.max_bps);
+        println!("  strategies: {:#06b}", executor.strategies);
+        println!("  paused: {}", executor.paused > 0);
+        println!("  uri: {}", executor.uri());
+        println!();
+    }
+    Ok(())
+}
+
 async fn log_treasury(rpc: &RpcClient) -> Result<(), anyhow::Error> {
     let treasury_address = ore_api::state::treasury_pda().0;
     let treasury = get_treasury(rpc).await?;
@@ -809,6 +1161,13 @@ async fn log_round(rpc: &RpcClient) -> Result<(), anyhow::Error> {
         amount_to_ui_amount(round.top_miner_reward(), TOKEN_DECIMALS)
     );
     println!("  Total miners: {}", round.total_miners);
+    println!("  Admin fee rate: {}%", round.admin_fee_rate as f64 / 100.0);
+    println!(
+        "  Protocol fee rate: {}%",
+        round.protocol_fee_rate as f64 / 100.0
+    );
+    println!("  Round slots: {}", round.round_slots);
+    println!("  Intermission slots: {}", round.intermission_slots);
     println!(
         "  Total deployed: {} SOL",
         lamports_to_sol(round.total_deployed())
@@ -843,6 +1202,9 @@ async fn log_miner(
     println!("Miner");
     println!("  address: {}", miner_address);
     println!("  authority: {}", miner.authority);
+    println!("  auto_return: {}", miner.auto_return > 0);
+    println!("  claim_delegate: {}", miner.claim_delegate);
+    println!("  claim_destination: {}", miner.claim_destination);
     println!("  deployed: {:?}", miner.deployed);
     println!("  cumulative: {:?}", miner.cumulative);
     println!(
@@ -896,36 +1258,48 @@ async fn log_config(rpc: &RpcClient) -> Result<(), anyhow::Error> {
     let config = get_config(&rpc).await?;
     println!("Admin");
     println!("  authority: {}", config.admin.authority);
-    println!("  fee_collector: {}", config.admin.fee_collector);
-    println!("  fee_rate: {}%", config.admin.fee_rate as f64 / 100.0);
+    println!("  pending_admin: {}", config.pending_admin);
+    println!("  fee_collector: {}", config.fee_collector());
+    println!("  fee_rate: {}%", config.admin_fee_rate() as f64 / 100.0);
     println!("Protocol");
     println!("  authority: {}", config.protocol.authority);
     println!("  fee_collector: {}", config.protocol.fee_collector);
-    println!("  fee_rate: {}%", config.protocol.fee_rate as f64 / 100.0);
+    println!("  fee_rate: {}%", config.protocol_fee_rate() as f64 / 100.0);
+    println!("  intermission_slots: {}", config.intermission_slots());
+    println!("  round_slots: {}", config.round_slots());
+    println!("  entropy_var: {}", config.entropy_var());
+    println!("  entropy_program: {}", config.entropy_program());
+    println!("  mass_round_id: {}", config.mass_round_id);
+    println!("Withdraw");
+    println!("  cutoff_slots: {}", config.withdraw_cutoff_slots);
     println!(
-        "  intermission_slots: {}",
-        config.protocol.intermission_slots
+        "  penalty_rate: {}%",
+        config.withdraw_penalty_rate as f64 / 100.0
     );
-    println!("  round_slots: {}", config.protocol.round_slots);
     Ok(())
 }
 
 async fn log_board(rpc: &RpcClient) -> Result<(), anyhow::Error> {
     let board = get_board(&rpc).await?;
+    let config = get_config(&rpc).await?;
+    let round = get_round(&rpc, board.round_id).await?;
     let clock = get_clock(&rpc).await?;
-    print_board(board, &clock);
+    print_board(board, &config, &round, &clock);
     Ok(())
 }
 
-fn print_board(board: Board, clock: &Clock) {
+fn print_board(board: Board, config: &Config, round: &Round, clock: &Clock) {
     let current_slot = clock.slot;
+    let end_slot = ore_api::sdk::round_end_slot(config, &board, current_slot);
+    let reset_slot = ore_api::sdk::round_reset_slot(config, &board, round, current_slot);
     println!("Board");
     println!("  Id: {:?}", board.round_id);
     println!("  Start slot: {}", board.start_slot);
-    println!("  End slot: {}", board.end_slot);
+    println!("  End slot: {}", end_slot);
+    println!("  Reset slot: {}", reset_slot);
     println!(
         "  Time remaining: {} sec",
-        (board.end_slot.saturating_sub(current_slot) as f64) * 0.4
+        (end_slot.saturating_sub(current_slot) as f64) * 0.4
     );
     println!(
         "  Production cost: {:?} SOL",
@@ -984,42 +1358,10 @@ async fn uncheckpointed(rpc: &RpcClient, payer: &solana_sdk::signer::keypair::Ke
         // Calculate what's owed using checkpoint logic.
         let mut total_owed = 0u64;
         if let Ok(round) = get_round(rpc, round_id).await {
-            if let Some(r) = round.rng() {
-                let winning_square = round.winning_square(r) as usize;
-                for (_addr, miner_deployed) in miners_list {
-                    // Replicate the checkpoint SOL calculation.
-                    // We need per-square deployed, so fetch the full miner.
-                    let miner_pda = *_addr;
-                    if let Ok(account) = rpc.get_account(&miner_pda).await {
-                        if let Ok(miner) = Miner::try_from_bytes(&account.data) {
-                            let mut rewards_sol: u64 = 0;
-                            for i in 0..25 {
-                                if miner.deployed[i] == 0 {
-                                    continue;
-                                }
-                                if i == winning_square {
-                                    // Proportional share of winning square budget (matches on-chain).
-                                    let sq_total = round.deployed[winning_square];
-                                    let sq_admin = (sq_total / 100).max(1);
-                                    let sq_returned = sq_total.saturating_sub(sq_admin);
-                                    rewards_sol += (miner.deployed[i] as u128 * sq_returned as u128 / sq_total as u128) as u64;
-                                } else {
-                                    // Proportional share of losing square budget (matches on-chain).
-                                    let sq_total = round.deployed[i];
-                                    let sq_admin = (sq_total / 100).max(1);
-                                    let sq_protocol = ((sq_total.saturating_sub(sq_admin)) / 10).max(1);
-                                    let sq_returned = sq_total.saturating_sub(sq_admin + sq_protocol);
-                                    rewards_sol += (miner.deployed[i] as u128 * sq_returned as u128 / sq_total as u128) as u64;
-                                }
-                            }
-                            total_owed += rewards_sol;
-                        }
-                    }
-                }
-            } else {
-                // No RNG — full refund.
-                for (_addr, deployed) in miners_list {
-                    total_owed += deployed;
+            for (addr, _) in miners_list {
+                // Replicate the checkpoint SOL calculation.
+                if let Some((_, miner)) = miners.iter().find(|(a, _)| a == addr) {
+                    total_owed += calculate_rewards(&round, miner, round.rng()).total_sol();
                 }
             }
         }
@@ -1086,13 +1428,10 @@ async fn topup_rounds(
     let miners = get_miners(rpc).await?;
 
     // Find insolvent rounds: group uncheckpointed miners by round.
-    let mut round_miners: HashMap<u64, Vec<(Pubkey, [u64; 25])>> = HashMap::new();
+    let mut round_miners: HashMap<u64, Vec<Miner>> = HashMap::new();
     for (_addr, miner) in &miners {
         if miner.checkpoint_id < miner.round_id && miner.round_id <= cutoff_round {
-            round_miners
-                .entry(miner.round_id)
-                .or_default()
-                .push((miner.authority, miner.deployed));
+            round_miners.entry(miner.round_id).or_default().push(*miner);
         }
     }
 
@@ -1116,33 +1455,14 @@ async fn topup_rounds(
         // Get current round account balance.
         let balance = rpc.get_balance(&round_pda).await?;
 
-        // Get round data to find winning square.
+        // Get round data to calculate rewards.
         let round = get_round(rpc, *round_id).await?;
-        let rng = round.rng();
 
         // Calculate total owed to uncheckpointed miners.
-        let mut total_owed = 0u64;
-        if let Some(r) = rng {
-            let winning_square = round.winning_square(r) as usize;
-            for (_authority, deployed) in &round_miners[round_id] {
-                if deployed[winning_square] > 0 {
-                    // Winning square: deployment minus admin fee + parimutuel share.
-                    let dep = deployed[winning_square];
-                    let admin_fee = (dep / 100).max(1);
-                    let base = dep - admin_fee;
-                    let parimutuel = ((round.total_returned_sol as u128 * dep as u128)
-                        / round.deployed[winning_square] as u128)
-                        as u64;
-                    total_owed += base + parimutuel;
-                }
-                // Losers get 0 under old parimutuel logic.
-            }
-        } else {
-            // No RNG — full refund for all miners.
-            for (_authority, deployed) in &round_miners[round_id] {
-                total_owed += deployed.iter().sum::<u64>();
-            }
-        }
+        let total_owed: u64 = round_miners[round_id]
+            .iter()
+            .map(|miner| calculate_rewards(&round, miner, round.rng()).total_sol())
+            .sum();
 
         // Calculate deficit.
         let needed = total_owed + round_rent_exempt;
@@ -1210,15 +1530,15 @@ async fn checkpoint_backfill(
     println!("Total miners: {}", miners.len());
 
     // Find miners with uncheckpointed rounds at or before the cutoff.
-    let mut targets: Vec<(Pubkey, u64)> = Vec::new();
+    let mut targets: Vec<(Pubkey, u64, u64)> = Vec::new();
     for (_addr, miner) in &miners {
         if miner.checkpoint_id < miner.round_id && miner.round_id <= cutoff_round {
-            targets.push((miner.authority, miner.round_id));
+            targets.push((miner.authority, miner.round_id, miner.automation_account_id()));
         }
     }
 
     // Sort by round ID.
-    targets.sort_by_key(|(_, round_id)| *round_id);
+    targets.sort_by_key(|(_, round_id, _)| *round_id);
 
     println!(
         "\nFound {} miners to checkpoint (rounds <= {})",
@@ -1231,20 +1551,29 @@ async fn checkpoint_backfill(
     }
 
     // Print summary.
-    for (authority, round_id) in &targets {
+    for (authority, round_id, _) in &targets {
         println!("  {} — round {}", authority, round_id);
     }
 
-    // Build checkpoint instructions.
-    let ixs: Vec<Instruction> = targets
+    // Build checkpoint instructions, batching miners by round.
+    let mut round_targets: HashMap<u64, Vec<(Pubkey, u64)>> = HashMap::new();
+    for (authority, round_id, automation_id) in &targets {
+        round_targets
+            .entry(*round_id)
+            .or_default()
+            .push((*authority, *automation_id));
+    }
+    let mut round_ids: Vec<u64> = round_targets.keys().copied().collect();
+    round_ids.sort();
+    let ixs: Vec<Instruction> = round_ids
         .iter()
-        .map(|(authority, round_id)| {
-            ore_api::sdk::checkpoint(payer.pubkey(), *authority, *round_id)
+        .flat_map(|round_id| {
+            ore_api::sdk::checkpoint_many(payer.pubkey(), *round_id, &round_targets[round_id])
         })
         .collect();
 
-    // Fire all off in batches, skipping failures.
-    submit_transaction_batches(rpc, payer, ixs, 5).await?;
+    // Fire all off, one batch instruction per transaction, skipping failures.
+    submit_transaction_batches(rpc, payer, ixs, 1).await?;
 
     println!(
         "\nDone. Attempted {} checkpoint transactions.",
@@ -1285,10 +1614,16 @@ async fn get_treasury(rpc: &RpcClient) -> Result<Treasury, anyhow::Error> {
     Ok(*treasury)
 }
 
-async fn get_automation(rpc: &RpcClient, authority: Pubkey) -> Result<Automation, anyhow::Error> {
-    let automation_pda = ore_api::state::automation_pda(authority);
-    let account = rpc.get_account(&automation_pda.0).await?;
-    let automation = Automation::try_from_bytes(&account.data)?;
+async fn get_automation(
+    rpc: &RpcClient,
+    authority: Pubkey,
+    id: u64,
+) -> Result<Automation, anyhow::Error> {
+    let automation_pda = ore_api::state::automation_pda_with_id(authority, id);
+    let mut data = rpc.get_account_data(&automation_pda.0).await?;
+    // Zero-fill automations that predate the current layout, as the program does on migration.
+    data.resize(data.len().max(8 + std::mem::size_of::<Automation>()), 0);
+    let automation = Automation::try_from_bytes(&data)?;
     Ok(*automation)
 }
 
@@ -1301,8 +1636,10 @@ async fn get_config(rpc: &RpcClient) -> Result<Config, anyhow::Error> {
 
 async fn get_miner(rpc: &RpcClient, authority: Pubkey) -> Result<Miner, anyhow::Error> {
     let miner_pda = ore_api::state::miner_pda(authority);
-    let account = rpc.get_account(&miner_pda.0).await?;
-    let miner = Miner::try_from_bytes(&account.data)?;
+    let mut data = rpc.get_account_data(&miner_pda.0).await?;
+    // Zero-fill miners that predate the current layout, as the program does on migration.
+    data.resize(data.len().max(8 + std::mem::size_of::<Miner>()), 0);
+    let miner = Miner::try_from_bytes(&data)?;
     Ok(*miner)
 }
 
@@ -1447,11 +1784,25 @@ async fn submit_transaction(
         }
         Err(e) => {
             println!("Error submitting transaction: {:?}", e);
+            if let Some(error) = decode_ore_error(&e) {
+                println!("Program error {}: {}", u32::from(error), error);
+            }
             Err(e.into())
         }
     }
 }
 
+/// Decodes an ORE program error from a failed transaction, if any.
+fn decode_ore_error(e: &solana_client::client_error::ClientError) -> Option<OreError> {
+    match e.get_transaction_error()? {
+        solana_sdk::transaction::TransactionError::InstructionError(
+            _,
+            solana_sdk::instruction::InstructionError::Custom(code),
+        ) => OreError::try_from(code).ok(),
+        _ => None,
+    }
+}
+
 async fn submit_transaction_no_confirm(
     rpc: &RpcClient,
     payer: &solana_sdk::signer::keypair::Keypair,
@@ -1514,7 +1865,10 @@ where
             let accounts = accounts
                 .into_iter()
                 .filter_map(|(pubkey, account)| {
-                    if let Ok(account) = T::try_from_bytes(&account.data) {
+                    // Zero-fill accounts that predate the current layout, as the program does on migration.
+                    let mut data = account.data;
+                    data.resize(data.len().max(8 + std::mem::size_of::<T>()), 0);
+                    if let Ok(account) = T::try_from_bytes(&data) {
                         Some((pubkey, account.clone()))
                     } else {
                         None
@@ -0,0 +1,42 @@
+use ore_api::prelude::*;
+use ore_api::sdk::program_log;
+use steel::*;
+
+/// Accepts a pending admin handover.
+pub fn process_accept_admin(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let clock = Clock::get()?;
+    let [signer_info, board_info, config_info, ore_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    board_info.has_address(&BOARD_ADDRESS)?;
+    let config = config_info
+        .is_writable()?
+        .has_seeds(&[CONFIG], &ore_api::ID)?
+        .as_account_mut::<Config>(&ore_api::ID)?
+        .assert_mut_err(
+            |c| c.pending_admin != Pubkey::default() && c.pending_admin == *signer_info.key,
+            OreError::NotAuthorized.into(),
+        )?;
+    ore_program.is_program(&ore_api::ID)?;
+
+    // Accept admin.
+    let old_authority = config.admin.authority;
+    config.admin.authority = *signer_info.key;
+    config.pending_admin = Pubkey::default();
+
+    // Emit event.
+    program_log(
+        &[board_info.clone(), ore_program.clone()],
+        AcceptAdminEvent {
+            disc: 6,
+            old_authority,
+            new_authority: config.admin.authority,
+            ts: clock.unix_timestamp,
+        }
+        .to_bytes(),
+    )?;
+
+    Ok(())
+}
@@ -1,12 +1,34 @@
 use std::u64;
 
 use ore_api::prelude::*;
+use ore_api::sdk::program_log;
 use steel::*;
 
+use crate::{migrate_automation, migrate_miner};
+
 /// Sets the executor.
 pub fn process_automate(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
     // Parse data.
-    let args = if let Ok(args) = AutomateV2::try_from_bytes(data) {
+    // Payloads without limits leave the automation's existing limits unchanged.
+    // Payloads without an ID configure the legacy automation (ID 0).
+    // Payloads without an executor requirement leave the automation's existing requirement unchanged.
+    let mut limits = None;
+    let mut id = 0;
+    let mut require_registered_executor = None;
+    let args = if let Ok(args) = AutomateV3::try_from_bytes(data) {
+        limits = Some(AutomationLimits::from_bytes(args.limits));
+        id = u64::from_le_bytes(args.id);
+        require_registered_executor = Some(args.require_registered_executor > 0);
+        AutomateV2 {
+            amount: args.amount,
+            deposit: args.deposit,
+            fee: args.fee,
+            mask: args.mask,
+            strategy: args.strategy,
+            reload: args.reload,
+            conditions: args.conditions,
+        }
+    } else if let Ok(args) = AutomateV2::try_from_bytes(data) {
         *args
     } else if let Ok(args) = Automate::try_from_bytes(data) {
         AutomateV2 {
@@ -37,13 +59,37 @@ pub fn process_automate(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramRes
         return Err(ProgramError::InvalidInstructionData);
     }
 
+    // The ExpectedValue strategy must select at least one square from a known mode.
+    if strategy == AutomationStrategy::ExpectedValue
+        && (conditions.ev_squares == 0
+            || conditions.ev_squares > 25
+            || mask == 0
+            || conditions.ev_mode > ExpectedValueMode::BestReturn as u16)
+    {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    // The Random strategy's selection version must be known.
+    if conditions.strategy_version > StrategyVersion::V2 as u16 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
     // Load accounts.
-    let [signer_info, automation_info, executor_info, miner_info, system_program] = accounts else {
+    let clock = Clock::get()?;
+    let [signer_info, automation_info, executor_info, miner_info, system_program, board_info, ore_program, executor_registry_info] =
+        accounts
+    else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
     signer_info.is_signer()?;
-    automation_info.is_writable()?;
+    automation_info
+        .is_writable()?
+        .has_address(&automation_pda_with_id(*signer_info.key, id).0)?;
+    board_info.has_address(&BOARD_ADDRESS)?;
+    executor_registry_info.has_address(&executor_pda(*executor_info.key).0)?;
     system_program.is_program(&system_program::ID)?;
+    ore_program.is_program(&ore_api::ID)?;
+    let log_accounts = [board_info.clone(), ore_program.clone()];
 
     // Do not allow permissionless execution with discretionary strategy.
     if (strategy == AutomationStrategy::Discretionary
@@ -65,7 +111,10 @@ pub fn process_automate(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramRes
         let miner = miner_info.as_account_mut::<Miner>(&ore_api::ID)?;
         miner.authority = *signer_info.key;
         miner.deployed = [0; 25];
+        miner.mass = [0; 25];
         miner.cumulative = [0; 25];
+        miner.topup_cumulative = [0; 25];
+        miner.topup = [0; 25];
         miner.checkpoint_fee = 0;
         miner.checkpoint_id = 0;
         miner.rewards_sol = 0;
@@ -76,6 +125,7 @@ pub fn process_automate(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramRes
         miner.auto_return = 1;
         miner
     } else {
+        migrate_miner(miner_info, signer_info)?;
         miner_info
             .as_account_mut::<Miner>(&ore_api::ID)?
             .assert_mut_err(
@@ -84,30 +134,53 @@ pub fn process_automate(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramRes
             )?
     };
 
+    // Migrate automation.
+    migrate_automation(automation_info, signer_info)?;
+
     // Close account if executor is Pubkey::default().
     if *executor_info.key == Pubkey::default() {
-        automation_info
+        let automation = automation_info
             .as_account_mut::<Automation>(&ore_api::ID)?
             .assert_mut_err(
                 |a| a.authority == *signer_info.key,
                 OreError::NotAuthorized.into(),
             )?;
-        automation_info.close(signer_info)?;
+        log_automation(
+            &log_accounts,
+            automation,
+            AutomationEventType::ClosedByUser,
+            automation_info.lamports(),
+            clock.unix_timestamp,
+        )?;
+        close_automation(automation_info, automation.id, miner, signer_info)?;
         return Ok(());
     }
 
     // Create automation.
-    let automation = if automation_info.data_is_empty() {
+    let is_new = automation_info.data_is_empty();
+    let automation = if is_new {
+        let authority_seed = signer_info.key.to_bytes();
+        let id_seed = id.to_le_bytes();
+        let seeds: &[&[u8]] = if id == 0 {
+            &[AUTOMATION, &authority_seed]
+        } else {
+            &[AUTOMATION, &authority_seed, &id_seed]
+        };
         create_program_account::<Automation>(
             automation_info,
             system_program,
             signer_info,
             &ore_api::ID,
-            &[AUTOMATION, &signer_info.key.to_bytes()],
+            seeds,
         )?;
         let automation = automation_info.as_account_mut::<Automation>(&ore_api::ID)?;
         automation.balance = 0;
         automation.authority = *signer_info.key;
+        automation.id = id;
+        automation.last_round_id = u64::MAX;
+        if id != 0 {
+            miner.automation_count += 1;
+        }
         automation
     } else {
         automation_info
@@ -120,7 +193,10 @@ pub fn process_automate(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramRes
 
     // Validate fee bps.
     if strategy == AutomationStrategy::DiscretionaryBps {
-        assert!(fee <= 100, "Fee bps must be less than 1%");
+        // Fee bps must be less than 1%.
+        if fee > 100 {
+            return Err(OreError::FeeRateTooHigh.into());
+        }
     }
 
     // Set strategy and mask.
@@ -132,6 +208,31 @@ pub fn process_automate(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramRes
     automation.strategy = strategy as u64;
     automation.reload = reload as u64;
     automation.conditions = conditions;
+    if let Some(require_registered_executor) = require_registered_executor {
+        automation.require_registered_executor = require_registered_executor as u64;
+    }
+
+    // Check the executor against its registry entry, which is required if the automation requires it.
+    if !executor_registry_info.data_is_empty() {
+        let executor = executor_registry_info.as_account::<Executor>(&ore_api::ID)?;
+        if executor.paused > 0 {
+            return Err(OreError::ExecutorPaused.into());
+        }
+        if !executor.supports(strategy) {
+            return Err(OreError::InvalidExecutor.into());
+        }
+        if fee > executor.max_fee_for(strategy) {
+            return Err(OreError::FeeRateTooHigh.into());
+        }
+    } else if automation.require_registered_executor > 0 {
+        return Err(OreError::InvalidExecutor.into());
+    }
+
+    // Set limits, restarting the round count.
+    if let Some(limits) = limits {
+        automation.limits = limits;
+        automation.rounds_deployed = 0;
+    }
 
     // Top up checkpoint fee.
     if miner.checkpoint_fee == 0 {
@@ -142,5 +243,78 @@ pub fn process_automate(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramRes
     // Transfer balance to executor.
     automation_info.collect(deposit, signer_info)?;
 
+    // Emit events.
+    if is_new {
+        log_automation(
+            &log_accounts,
+            automation,
+            AutomationEventType::Created,
+            deposit,
+            clock.unix_timestamp,
+        )?;
+    } else {
+        log_automation(
+            &log_accounts,
+            automation,
+            AutomationEventType::Updated,
+            0,
+            clock.unix_timestamp,
+        )?;
+        if deposit > 0 {
+            log_automation(
+                &log_accounts,
+                automation,
+                AutomationEventType::Deposited,
+                deposit,
+                clock.unix_timestamp,
+            )?;
+        }
+    }
+
     Ok(())
 }
+
+/// Closes an automation account.
+/// Indexed automations are released from the miner's live automation count.
+pub fn close_automation<'a>(
+    automation_info: &AccountInfo<'a>,
+    id: u64,
+    miner: &mut Miner,
+    recipient_info: &AccountInfo<'a>,
+) -> ProgramResult {
+    if id != 0 {
+        miner.automation_count = miner.automation_count.saturating_sub(1);
+    }
+    automation_info.close(recipient_info)
+}
+
+/// Emits an automation event with a snapshot of the automation's state.
+pub fn log_automation(
+    accounts: &[AccountInfo<'_>],
+    automation: &Automation,
+    event_type: AutomationEventType,
+    sol_amount: u64,
+    ts: i64,
+) -> ProgramResult {
+    program_log(
+        accounts,
+        AutomationEvent {
+            disc: 8,
+            authority: automation.authority,
+            id: automation.id,
+            executor: automation.executor,
+            event_type: event_type as u64,
+            sol_amount,
+            amount: automation.amount,
+            balance: automation.balance,
+            fee: automation.fee,
+            strategy: automation.strategy,
+            mask: automation.mask,
+            reload: automation.reload,
+            conditions: automation.conditions,
+            limits: automation.limits,
+            ts,
+        }
+        .to_bytes(),
+    )
+}
@@ -41,6 +41,35 @@ pub fn process_bury(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult
         amount,
     )?;
 
+    // Bury ORE.
+    bury_ore(
+        board_info,
+        mint_info,
+        treasury_info,
+        treasury_ore_info,
+        stake_treasury_info,
+        stake_treasury_tokens_info,
+        stake_vesting_info,
+        token_program,
+        ore_program,
+        amount,
+    )
+}
+
+/// Shares 10% of the ORE held in the treasury with stakers, and burns the rest.
+#[allow(clippy::too_many_arguments)]
+pub fn bury_ore<'a>(
+    board_info: &AccountInfo<'a>,
+    mint_info: &AccountInfo<'a>,
+    treasury_info: &AccountInfo<'a>,
+    treasury_ore_info: &AccountInfo<'a>,
+    stake_treasury_info: &AccountInfo<'a>,
+    stake_treasury_tokens_info: &AccountInfo<'a>,
+    stake_vesting_info: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    ore_program: &AccountInfo<'a>,
+    amount: u64,
+) -> ProgramResult {
     // Share 10% of buyback ORE with stakers
     let shared_amount = amount / 10;
     invoke_signed(
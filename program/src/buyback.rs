@@ -47,7 +47,9 @@ pub fn process_buyback(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResu
         treasury_sol_info.as_associated_token_account(treasury_info.key, &SOL_MINT)?;
     let pre_swap_ore_balance = treasury_ore.amount();
     let total_sol_balance = treasury_sol.amount();
-    assert!(total_sol_balance > 0);
+    if total_sol_balance == 0 {
+        return Err(OreError::SwapBalanceEmpty.into());
+    }
 
     // Transfer liq percentage to the liq manager.
     let liq_amount = total_sol_balance * LIQ_PCT / 100;
@@ -67,7 +69,9 @@ pub fn process_buyback(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResu
     let treasury_sol =
         treasury_sol_info.as_associated_token_account(treasury_info.key, &SOL_MINT)?;
     let pre_swap_sol_balance = treasury_sol.amount();
-    assert!(pre_swap_sol_balance > 0);
+    if pre_swap_sol_balance == 0 {
+        return Err(OreError::SwapBalanceEmpty.into());
+    }
 
     // Record pre-swap mint supply.
     let pre_swap_mint_supply = ore_mint.supply();
@@ -108,19 +112,23 @@ pub fn process_buyback(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResu
 
     // Record post-swap treasury lamports.
     let post_swap_treasury_lamports = treasury_info.lamports();
-    assert_eq!(
-        post_swap_treasury_lamports, pre_swap_treasury_lamports,
-        "Treasury lamports changed during swap: {} -> {}",
-        pre_swap_treasury_lamports, post_swap_treasury_lamports
-    );
+    if post_swap_treasury_lamports != pre_swap_treasury_lamports {
+        sol_log(&format!(
+            "Treasury lamports changed during swap: {} -> {}",
+            pre_swap_treasury_lamports, post_swap_treasury_lamports
+        ));
+        return Err(OreError::SwapTreasuryLamportsChanged.into());
+    }
 
     // Record post-swap mint supply.
     let post_swap_mint_supply = mint_info.as_mint()?.supply();
-    assert_eq!(
-        post_swap_mint_supply, pre_swap_mint_supply,
-        "Mint supply changed during swap: {} -> {}",
-        pre_swap_mint_supply, post_swap_mint_supply
-    );
+    if post_swap_mint_supply != pre_swap_mint_supply {
+        sol_log(&format!(
+            "Mint supply changed during swap: {} -> {}",
+            pre_swap_mint_supply, post_swap_mint_supply
+        ));
+        return Err(OreError::SwapMintSupplyChanged.into());
+    }
 
     // Record post-swap balances.
     let treasury_ore =
@@ -129,9 +137,13 @@ pub fn process_buyback(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResu
         treasury_sol_info.as_associated_token_account(treasury_info.key, &SOL_MINT)?;
     let post_swap_ore_balance = treasury_ore.amount();
     let post_swap_sol_balance = treasury_sol.amount();
+    if post_swap_sol_balance != 0 {
+        return Err(OreError::SwapIncomplete.into());
+    }
+    if post_swap_ore_balance < pre_swap_ore_balance {
+        return Err(OreError::SwapOreDecreased.into());
+    }
     let total_ore = post_swap_ore_balance - pre_swap_ore_balance;
-    assert_eq!(post_swap_sol_balance, 0);
-    assert!(post_swap_ore_balance >= pre_swap_ore_balance);
     sol_log(
         &format!(
             "📈 Swapped {} SOL into {} ORE",
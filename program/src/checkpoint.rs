@@ -1,35 +1,76 @@
 use ore_api::prelude::*;
+use ore_api::sdk::program_log;
 use solana_program::{log::sol_log, native_token::lamports_to_sol, rent::Rent};
 use spl_token::amount_to_ui_amount;
 use steel::*;
 
+use crate::{migrate_automation, migrate_miner, migrate_round};
+
 /// Checkpoints a miner's rewards.
 pub fn process_checkpoint(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
     // Load accounts.
     let clock = Clock::get()?;
-    let [signer_info, authority_info, automation_info, board_info, miner_info, round_info, treasury_info, system_program] =
+    let [signer_info, authority_info, automation_info, board_info, miner_info, round_info, treasury_info, system_program, ore_program] =
         accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
     signer_info.is_signer()?;
-    authority_info.is_writable()?;
-    automation_info.has_seeds(&[AUTOMATION, &authority_info.key.to_bytes()], &ore_api::ID)?;
-    let board = board_info
-        .has_address(&BOARD_ADDRESS)?
-        .as_account::<Board>(&ore_api::ID)?;
-    let miner = miner_info
-        .has_seeds(&[MINER, &authority_info.key.to_bytes()], &ore_api::ID)?
-        .as_account_mut::<Miner>(&ore_api::ID)?
-        .assert_mut(|m| m.authority == *authority_info.key)?;
+    board_info.has_address(&BOARD_ADDRESS)?;
     let treasury = treasury_info
         .has_address(&TREASURY_ADDRESS)?
         .as_account_mut::<Treasury>(&ore_api::ID)?;
     system_program.is_program(&system_program::ID)?;
+    ore_program.is_program(&ore_api::ID)?;
+
+    // Checkpoint the miner.
+    let bot_fee = checkpoint_miner(
+        signer_info,
+        authority_info,
+        automation_info,
+        board_info,
+        miner_info,
+        round_info,
+        treasury,
+        ore_program,
+        &clock,
+    )?;
+    if bot_fee > 0 {
+        miner_info.send(bot_fee, &signer_info);
+    }
+
+    Ok(())
+}
+
+/// Checkpoints a single miner against the provided round.
+/// Returns the bot fee owed to the signer, which the caller is responsible for transferring out of the miner account.
+#[allow(clippy::too_many_arguments)]
+pub fn checkpoint_miner<'a>(
+    signer_info: &AccountInfo<'a>,
+    authority_info: &AccountInfo<'a>,
+    automation_info: &AccountInfo<'a>,
+    board_info: &AccountInfo<'a>,
+    miner_info: &AccountInfo<'a>,
+    round_info: &AccountInfo<'a>,
+    treasury: &mut Treasury,
+    ore_program: &AccountInfo<'a>,
+    clock: &Clock,
+) -> Result<u64, ProgramError> {
+    // Load accounts.
+    let board = board_info.as_account::<Board>(&ore_api::ID)?;
+    authority_info.is_writable()?;
+    miner_info.has_seeds(&[MINER, &authority_info.key.to_bytes()], &ore_api::ID)?;
+    migrate_miner(miner_info, signer_info)?;
+    let miner = miner_info
+        .as_account_mut::<Miner>(&ore_api::ID)?
+        .assert_mut(|m| m.authority == *authority_info.key)?;
+
+    // The automation account must be the one that funded the miner's round.
+    automation_info.has_address(&miner.automation_address())?;
 
     // If miner has already checkpointed this round, return.
     if miner.checkpoint_id == miner.round_id {
-        return Ok(());
+        return Ok(0);
     }
 
     // If round account is empty, verify the correct account was provided.
@@ -39,15 +80,28 @@ pub fn process_checkpoint(accounts: &[AccountInfo<'_>], _data: &[u8]) -> Program
         sol_log(&format!("Round account is empty").as_str());
         round_info.has_seeds(&[ROUND, &miner.round_id.to_le_bytes()], &ore_api::ID)?;
         miner.checkpoint_id = miner.round_id;
-        return Ok(());
+        log_checkpoint(
+            board_info,
+            ore_program,
+            CheckpointEvent {
+                disc: 7,
+                authority: miner.authority,
+                round_id: miner.round_id,
+                is_expired: 1,
+                ts: clock.unix_timestamp,
+                ..Default::default()
+            },
+        )?;
+        return Ok(0);
     }
 
     // If round is current round, or the miner round ID does not match the provided round, return.
+    migrate_round(round_info, signer_info)?;
     let round = round_info.as_account_mut::<Round>(&ore_api::ID)?; // Round has been closed.
     sol_log(&format!("Round ID: {}", round.id).as_str());
     if round.id == board.round_id || round.id != miner.round_id || round.slot_hash == [0; 32] {
         sol_log(&format!("Round not valid").as_str());
-        return Ok(());
+        return Ok(0);
     }
 
     // Ensure round is not expired.
@@ -55,7 +109,19 @@ pub fn process_checkpoint(accounts: &[AccountInfo<'_>], _data: &[u8]) -> Program
     if clock.slot >= round.expires_at {
         sol_log(&format!("Round expired").as_str());
         miner.checkpoint_id = miner.round_id;
-        return Ok(());
+        log_checkpoint(
+            board_info,
+            ore_program,
+            CheckpointEvent {
+                disc: 7,
+                authority: miner.authority,
+                round_id: miner.round_id,
+                is_expired: 1,
+                ts: clock.unix_timestamp,
+                ..Default::default()
+            },
+        )?;
+        return Ok(0);
     }
 
     // Calculate bot fee.
@@ -67,104 +133,44 @@ pub fn process_checkpoint(accounts: &[AccountInfo<'_>], _data: &[u8]) -> Program
     }
 
     // Calculate miner rewards.
-    let mut rewards_sol = 0;
-    let mut rewards_ore = 0;
-
-    // Get the RNG.
-    if let Some(r) = round.rng() {
-        // Get the winning square.
-        let winning_square = round.winning_square(r) as usize;
-
-        // Iterate over all squares.
-        for i in 0..25 {
-            // Continue if miner did not deploy to this square.
-            if miner.deployed[i] == 0 {
-                continue;
-            }
-
-            // Miner deployed to the winning square.
-            if i == winning_square {
-                // Sanity check.
-                assert!(
-                    round.deployed[winning_square] >= miner.deployed[winning_square],
-                    "Invalid round deployed amount"
-                );
-
-                // Calculate SOL rewards.
-                let sq_total = round.deployed[winning_square];
-                let sq_admin = (sq_total / 100).max(1);
-                let sq_returned = sq_total.saturating_sub(sq_admin);
-                rewards_sol += (miner.deployed[winning_square] as u128 * sq_returned as u128 / sq_total as u128) as u64;
-
-                // Calculate ORE rewards.
-                if round.top_miner == SPLIT_ADDRESS {
-                    // If round is split, split the reward evenly among all miners.
-                    rewards_ore = ((round.top_miner_reward() as u128
-                        * miner.deployed[winning_square] as u128)
-                        / round.deployed[winning_square] as u128)
-                        as u64;
-                    sol_log(
-                        &format!(
-                            "Split rewards: {} ORE",
-                            amount_to_ui_amount(rewards_ore, TOKEN_DECIMALS)
-                        )
-                        .as_str(),
-                    );
-                } else {
-                    // If round is not split, payout to the top miner.
-                    let top_miner_sample = round.top_miner_sample(r, winning_square);
-                    if top_miner_sample >= miner.cumulative[winning_square]
-                        && top_miner_sample
-                            < miner.cumulative[winning_square] + miner.deployed[winning_square]
-                    {
-                        rewards_ore = round.top_miner_reward();
-                        round.top_miner = miner.authority;
-                        sol_log(
-                            &format!(
-                                "Top miner rewards: {} ORE",
-                                amount_to_ui_amount(rewards_ore, TOKEN_DECIMALS)
-                            )
-                            .as_str(),
-                        );
-                    }
-                }
-
-                // Calculate motherlode rewards.
-                if round.motherlode > 0 {
-                    let motherload_rewards = ((round.motherlode as u128
-                        * miner.deployed[winning_square] as u128)
-                        / round.deployed[winning_square] as u128)
-                        as u64;
-                    sol_log(
-                        &format!(
-                            "Motherlode rewards: {} ORE",
-                            amount_to_ui_amount(motherload_rewards, TOKEN_DECIMALS)
-                        )
-                        .as_str(),
-                    );
-                    rewards_ore += motherload_rewards;
-                }
-            } else {
-                // Calculate returned SOL rewards.
-                let sq_total = round.deployed[i];
-                let sq_admin = (sq_total / 100).max(1);
-                let sq_protocol = ((sq_total.saturating_sub(sq_admin)) / 10).max(1);
-                let sq_returned = sq_total.saturating_sub(sq_admin + sq_protocol);
-                rewards_sol += (miner.deployed[i] as u128 * sq_returned as u128 / sq_total as u128) as u64;
-            }
+    let rng = round.rng();
+    if let Some(r) = rng {
+        // Sanity check.
+        let winning_square = round.winning_square(r);
+        if round.deployed[winning_square] < miner.deployed[winning_square] {
+            return Err(OreError::InvalidRoundDeployed.into());
         }
     } else {
         // Sanity check.
         // If there is no rng, total deployed should have been reset to zero.
-        assert!(
-            round.total_deployed() == 0,
-            "Round total deployed should be zero."
-        );
-
-        // Round has no slot hash, refund all SOL.
-        let refund_amount = miner.deployed.iter().sum::<u64>();
-        sol_log(&format!("Refunding {} SOL", lamports_to_sol(refund_amount)).as_str());
-        rewards_sol = refund_amount;
+        if round.total_deployed() != 0 {
+            return Err(OreError::RoundNotRefunded.into());
+        }
+    }
+    let rewards = calculate_rewards(round, miner, rng);
+    let rewards_sol = rewards.total_sol();
+    let rewards_ore = rewards.total_ore();
+    if rewards.is_refund {
+        sol_log(&format!("Refunding {} SOL", lamports_to_sol(rewards_sol)));
+    }
+    if rewards.split_ore > 0 {
+        sol_log(&format!(
+            "Split rewards: {} ORE",
+            amount_to_ui_amount(rewards.split_ore, TOKEN_DECIMALS)
+        ));
+    }
+    if rewards.top_miner_ore > 0 {
+        round.top_miner = miner.authority;
+        sol_log(&format!(
+            "Top miner rewards: {} ORE",
+            amount_to_ui_amount(rewards.top_miner_ore, TOKEN_DECIMALS)
+        ));
+    }
+    if rewards.motherlode_ore > 0 {
+        sol_log(&format!(
+            "Motherlode rewards: {} ORE",
+            amount_to_ui_amount(rewards.motherlode_ore, TOKEN_DECIMALS)
+        ));
     }
 
     // Checkpoint rewards.
@@ -180,8 +186,9 @@ pub fn process_checkpoint(accounts: &[AccountInfo<'_>], _data: &[u8]) -> Program
     // Update treasury.
     treasury.total_unclaimed += rewards_ore;
 
-    // Load the automation account if it exists.
-    let automation = if !automation_info.data_is_empty() {
+    // Load the automation account if it exists and funded the round.
+    migrate_automation(automation_info, signer_info)?;
+    let automation = if !automation_info.data_is_empty() && !miner.is_manually_funded() {
         let automation = automation_info
             .as_account_mut::<Automation>(&ore_api::ID)?
             .assert_mut(|a| a.authority == miner.authority)?;
@@ -192,6 +199,7 @@ pub fn process_checkpoint(accounts: &[AccountInfo<'_>], _data: &[u8]) -> Program
     };
 
     // Do SOL transfers.
+    let mut sol_destination = 0; // Miner
     if rewards_sol > 0 {
         match automation {
             Some(automation) if automation.reload > 0 => {
@@ -201,6 +209,7 @@ pub fn process_checkpoint(accounts: &[AccountInfo<'_>], _data: &[u8]) -> Program
                 miner.rewards_sol -= rewards_sol;
                 automation.balance += rewards_sol;
                 round_info.send(rewards_sol, &automation_info);
+                sol_destination = 2;
             }
             _ => {
                 if miner.auto_return > 0 {
@@ -210,6 +219,7 @@ pub fn process_checkpoint(accounts: &[AccountInfo<'_>], _data: &[u8]) -> Program
                     );
                     miner.rewards_sol -= rewards_sol;
                     round_info.send(rewards_sol, &authority_info);
+                    sol_destination = 1;
                 } else {
                     sol_log(
                         &format!("Sending {} SOL to miner", lamports_to_sol(rewards_sol)).as_str(),
@@ -219,16 +229,40 @@ pub fn process_checkpoint(accounts: &[AccountInfo<'_>], _data: &[u8]) -> Program
             }
         }
     }
-    if bot_fee > 0 {
-        miner_info.send(bot_fee, &signer_info);
-    }
 
-    // Assert miner account has sufficient funds for rent and rewards.
+    // Assert miner account has sufficient funds for rent, rewards, and the bot fee.
     let required_rent = Rent::get()?.minimum_balance(Miner::SIZE);
-    assert!(
-        miner_info.lamports() >= required_rent + miner.checkpoint_fee + miner.rewards_sol,
-        "Miner does not have sufficient funds for rent and rewards"
-    );
+    if miner_info.lamports() < required_rent + miner.checkpoint_fee + miner.rewards_sol + bot_fee {
+        return Err(OreError::InsufficientMinerBalance.into());
+    }
 
-    Ok(())
+    // Emit event.
+    log_checkpoint(
+        board_info,
+        ore_program,
+        CheckpointEvent {
+            disc: 7,
+            authority: miner.authority,
+            round_id: round.id,
+            sol_returned: rewards_sol,
+            top_miner_ore: rewards.top_miner_ore,
+            split_ore: rewards.split_ore,
+            motherlode_ore: rewards.motherlode_ore,
+            is_refund: rewards.is_refund as u64,
+            is_expired: 0,
+            bot_fee,
+            sol_destination,
+            ts: clock.unix_timestamp,
+        },
+    )?;
+
+    Ok(bot_fee)
+}
+
+fn log_checkpoint<'a>(
+    board_info: &AccountInfo<'a>,
+    ore_program: &AccountInfo<'a>,
+    event: CheckpointEvent,
+) -> ProgramResult {
+    program_log(&[board_info.clone(), ore_program.clone()], event.to_bytes())
 }
@@ -0,0 +1,52 @@
+use ore_api::prelude::*;
+use steel::*;
+
+use crate::checkpoint_miner;
+
+/// Checkpoints the rewards of many miners in a single round.
+pub fn process_checkpoint_many(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let clock = Clock::get()?;
+    let (shared_accounts, miner_accounts) = accounts.split_at(accounts.len().min(6));
+    let [signer_info, board_info, round_info, treasury_info, system_program, ore_program] =
+        shared_accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    board_info.has_address(&BOARD_ADDRESS)?;
+    let treasury = treasury_info
+        .has_address(&TREASURY_ADDRESS)?
+        .as_account_mut::<Treasury>(&ore_api::ID)?;
+    system_program.is_program(&system_program::ID)?;
+    ore_program.is_program(&ore_api::ID)?;
+    if miner_accounts.is_empty() || miner_accounts.len() % 3 != 0 {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    // Checkpoint each miner, collecting the bot fee from each.
+    let mut total_bot_fee = 0;
+    for chunk in miner_accounts.chunks(3) {
+        let [authority_info, miner_info, automation_info] = chunk else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        let bot_fee = checkpoint_miner(
+            signer_info,
+            authority_info,
+            automation_info,
+            board_info,
+            miner_info,
+            round_info,
+            treasury,
+            ore_program,
+            &clock,
+        )?;
+        **miner_info.try_borrow_mut_lamports()? -= bot_fee;
+        total_bot_fee += bot_fee;
+    }
+
+    // Pay the bot fees in a single transfer.
+    **signer_info.try_borrow_mut_lamports()? += total_bot_fee;
+
+    Ok(())
+}
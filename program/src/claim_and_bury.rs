@@ -0,0 +1,76 @@
+use ore_api::prelude::*;
+use ore_api::sdk::program_log;
+use solana_program::log::sol_log;
+use spl_token::amount_to_ui_amount;
+use steel::*;
+
+use crate::{bury_ore, migrate_miner};
+
+/// Claims ORE mining rewards and buries them, without moving them out of the treasury.
+pub fn process_claim_and_bury(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = ClaimAndBury::try_from_bytes(data)?;
+    let bps = u64::from_le_bytes(args.bps);
+
+    // Load accounts.
+    let clock = Clock::get()?;
+    let [signer_info, board_info, miner_info, mint_info, treasury_info, treasury_ore_info, stake_treasury_info, stake_treasury_tokens_info, stake_vesting_info, token_program, ore_program, ore_stake_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    board_info.has_address(&BOARD_ADDRESS)?;
+    miner_info.has_seeds(&[MINER, &signer_info.key.to_bytes()], &ore_api::ID)?;
+    migrate_miner(miner_info, signer_info)?;
+    let miner = miner_info
+        .as_account_mut::<Miner>(&ore_api::ID)?
+        .assert_mut_err(
+            |m| m.authority == *signer_info.key,
+            OreError::NotAuthorized.into(),
+        )?;
+    mint_info.has_address(&MINT_ADDRESS)?.as_mint()?;
+    let treasury = treasury_info
+        .has_address(&TREASURY_ADDRESS)?
+        .as_account_mut::<Treasury>(&ore_api::ID)?;
+    treasury_ore_info.as_associated_token_account(treasury_info.key, &MINT_ADDRESS)?;
+    token_program.is_program(&spl_token::ID)?;
+    ore_program.is_program(&ore_api::ID)?;
+    ore_stake_program.is_program(&ore_stake_api::ID)?;
+
+    // Normalize amount.
+    let (amount, fee) = miner.claim_ore(&clock, treasury, bps);
+
+    sol_log(&format!(
+        "Claiming {} ORE to bury. Paid {} ORE in refining fees.",
+        amount_to_ui_amount(amount, TOKEN_DECIMALS),
+        amount_to_ui_amount(fee, TOKEN_DECIMALS)
+    ));
+
+    // Emit claim event.
+    program_log(
+        &[board_info.clone(), ore_program.clone()],
+        ClaimEvent {
+            disc: 4,
+            authority: miner.authority,
+            amount,
+            claim_type: 3, // ORE buried
+            ts: clock.unix_timestamp,
+        }
+        .to_bytes(),
+    )?;
+
+    // Bury the claimed ORE, which is already held by the treasury.
+    bury_ore(
+        board_info,
+        mint_info,
+        treasury_info,
+        treasury_ore_info,
+        stake_treasury_info,
+        stake_treasury_tokens_info,
+        stake_vesting_info,
+        token_program,
+        ore_program,
+        amount,
+    )
+}
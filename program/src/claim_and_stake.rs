@@ -0,0 +1,93 @@
+use ore_api::prelude::*;
+use solana_program::{log::sol_log, program::invoke};
+use spl_token::amount_to_ui_amount;
+use steel::*;
+
+use crate::claim_ore;
+
+/// Claims ORE mining rewards and deposits them into the stake program.
+/// The stake program only accepts deposits from the staker's own token account, so rewards pass
+/// through the signer's token account within this instruction rather than moving from the treasury directly.
+/// The instruction fails unless the signer's token balance is unchanged once the deposit completes.
+pub fn process_claim_and_stake(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = ClaimAndStake::try_from_bytes(data)?;
+    let bps = u64::from_le_bytes(args.bps);
+    let compound_fee = u64::from_le_bytes(args.compound_fee);
+    let compound_fee_deposit = u64::from_le_bytes(args.compound_fee_deposit);
+
+    // Load accounts.
+    let [signer_info, board_info, miner_info, mint_info, sender_info, treasury_info, treasury_tokens_info, stake_info, stake_tokens_info, stake_treasury_info, stake_vesting_info, system_program, token_program, associated_token_program, ore_program, ore_stake_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    ore_stake_program.is_program(&ore_stake_api::ID)?;
+
+    // Record the signer's token balance before the claim.
+    let pre_claim_balance = if sender_info.data_is_empty() {
+        0
+    } else {
+        sender_info
+            .as_associated_token_account(signer_info.key, &MINT_ADDRESS)?
+            .amount()
+    };
+
+    // Claim ORE into the signer's token account, the only account the stake program will deposit from.
+    let amount = claim_ore(
+        signer_info,
+        signer_info,
+        board_info,
+        miner_info,
+        mint_info,
+        signer_info,
+        sender_info,
+        treasury_info,
+        treasury_tokens_info,
+        system_program,
+        token_program,
+        associated_token_program,
+        ore_program,
+        bps,
+        2, // ORE staked
+    )?;
+
+    // Deposit the claimed ORE into the stake program.
+    invoke(
+        &ore_stake_api::sdk::deposit(
+            *signer_info.key,
+            *signer_info.key,
+            amount,
+            compound_fee,
+            compound_fee_deposit,
+        ),
+        &[
+            signer_info.clone(),
+            mint_info.clone(),
+            sender_info.clone(),
+            stake_info.clone(),
+            stake_tokens_info.clone(),
+            stake_treasury_info.clone(),
+            stake_vesting_info.clone(),
+            system_program.clone(),
+            token_program.clone(),
+            associated_token_program.clone(),
+            ore_stake_program.clone(),
+        ],
+    )?;
+
+    // Require every claimed token to have been deposited.
+    let post_deposit_balance = sender_info
+        .as_associated_token_account(signer_info.key, &MINT_ADDRESS)?
+        .amount();
+    if post_deposit_balance != pre_claim_balance {
+        return Err(OreError::StakeDepositIncomplete.into());
+    }
+
+    sol_log(&format!(
+        "Staked {} ORE",
+        amount_to_ui_amount(amount, TOKEN_DECIMALS)
+    ));
+
+    Ok(())
+}
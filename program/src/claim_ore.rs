@@ -4,29 +4,104 @@ use solana_program::log::sol_log;
 use spl_token::amount_to_ui_amount;
 use steel::*;
 
+use crate::migrate_miner;
+
 /// Claims a block reward.
 pub fn process_claim_ore(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
-    // Load data.
-    let mut bps = DENOMINATOR_BPS;
-    if let Ok(args) = ClaimORE::try_from_bytes(data) {
-        bps = u64::from_le_bytes(args.bps);
+    if let Ok(args) = ClaimOREV2::try_from_bytes(data) {
+        // Claim to the provided recipient.
+        let bps = u64::from_le_bytes(args.bps);
+        let [signer_info, authority_info, board_info, miner_info, mint_info, recipient_info, recipient_tokens_info, treasury_info, treasury_tokens_info, system_program, token_program, associated_token_program, ore_program] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        recipient_info.has_address(&Pubkey::new_from_array(args.recipient))?;
+        claim_ore(
+            signer_info,
+            authority_info,
+            board_info,
+            miner_info,
+            mint_info,
+            recipient_info,
+            recipient_tokens_info,
+            treasury_info,
+            treasury_tokens_info,
+            system_program,
+            token_program,
+            associated_token_program,
+            ore_program,
+            bps,
+            1, // ORE
+        )?;
+    } else {
+        // Claim to the authority.
+        let mut bps = DENOMINATOR_BPS;
+        if let Ok(args) = ClaimORE::try_from_bytes(data) {
+            bps = u64::from_le_bytes(args.bps);
+        }
+        let [signer_info, board_info, miner_info, mint_info, recipient_tokens_info, treasury_info, treasury_tokens_info, system_program, token_program, associated_token_program, ore_program] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        claim_ore(
+            signer_info,
+            signer_info,
+            board_info,
+            miner_info,
+            mint_info,
+            signer_info,
+            recipient_tokens_info,
+            treasury_info,
+            treasury_tokens_info,
+            system_program,
+            token_program,
+            associated_token_program,
+            ore_program,
+            bps,
+            1, // ORE
+        )?;
     }
 
+    Ok(())
+}
+
+/// Claims ORE from a miner account into the recipient's token account.
+/// Returns the amount claimed, net of refining fees.
+#[allow(clippy::too_many_arguments)]
+pub fn claim_ore<'a>(
+    signer_info: &AccountInfo<'a>,
+    authority_info: &AccountInfo<'a>,
+    board_info: &AccountInfo<'a>,
+    miner_info: &AccountInfo<'a>,
+    mint_info: &AccountInfo<'a>,
+    recipient_info: &AccountInfo<'a>,
+    recipient_tokens_info: &AccountInfo<'a>,
+    treasury_info: &AccountInfo<'a>,
+    treasury_tokens_info: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    associated_token_program: &AccountInfo<'a>,
+    ore_program: &AccountInfo<'a>,
+    bps: u64,
+    claim_type: u64,
+) -> Result<u64, ProgramError> {
     // Load accounts.
     let clock = Clock::get()?;
-    let [signer_info, board_info, miner_info, mint_info, recipient_info, treasury_info, treasury_tokens_info, system_program, token_program, associated_token_program, ore_program] =
-        accounts
-    else {
-        return Err(ProgramError::NotEnoughAccountKeys);
-    };
     signer_info.is_signer()?;
     board_info.has_address(&BOARD_ADDRESS)?;
+    miner_info.has_seeds(&[MINER, &authority_info.key.to_bytes()], &ore_api::ID)?;
+    migrate_miner(miner_info, signer_info)?;
     let miner = miner_info
-        .has_seeds(&[MINER, &signer_info.key.to_bytes()], &ore_api::ID)?
         .as_account_mut::<Miner>(&ore_api::ID)?
-        .assert_mut(|m| m.authority == *signer_info.key)?;
+        .assert_mut(|m| m.authority == *authority_info.key)?
+        .assert_mut_err(
+            |m| m.can_claim(signer_info.key, recipient_info.key),
+            OreError::NotAuthorized.into(),
+        )?;
     mint_info.has_address(&MINT_ADDRESS)?.as_mint()?;
-    recipient_info.is_writable()?;
+    recipient_tokens_info.is_writable()?;
     let treasury = treasury_info
         .has_address(&TREASURY_ADDRESS)?
         .as_account_mut::<Treasury>(&ore_api::ID)?;
@@ -37,18 +112,21 @@ pub fn process_claim_ore(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramRe
     ore_program.is_program(&ore_api::ID)?;
 
     // Load recipient.
-    if recipient_info.data_is_empty() {
+    if recipient_tokens_info.data_is_empty() {
         create_associated_token_account(
-            signer_info,
             signer_info,
             recipient_info,
+            recipient_tokens_info,
             mint_info,
             system_program,
             token_program,
             associated_token_program,
         )?;
     } else {
-        recipient_info.as_associated_token_account(signer_info.key, mint_info.key)?;
+        recipient_tokens_info
+            .as_token_account()?
+            .assert(|t| t.mint() == *mint_info.key)?
+            .assert(|t| t.owner() == *recipient_info.key)?;
     }
 
     // Normalize amount.
@@ -67,7 +145,7 @@ pub fn process_claim_ore(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramRe
     transfer_signed(
         treasury_info,
         treasury_tokens_info,
-        recipient_info,
+        recipient_tokens_info,
         token_program,
         amount,
         &[TREASURY],
@@ -80,11 +158,11 @@ pub fn process_claim_ore(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramRe
             disc: 4,
             authority: miner.authority,
             amount,
-            claim_type: 1, // ORE
+            claim_type,
             ts: clock.unix_timestamp,
         }
         .to_bytes(),
     )?;
 
-    Ok(())
+    Ok(amount)
 }
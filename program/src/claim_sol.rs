@@ -3,19 +3,67 @@ use ore_api::sdk::program_log;
 use solana_program::{log::sol_log, native_token::lamports_to_sol};
 use steel::*;
 
+use crate::migrate_miner;
+
 /// Claims a block reward.
-pub fn process_claim_sol(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+pub fn process_claim_sol(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    if let Ok(args) = ClaimSOLV2::try_from_bytes(data) {
+        // Claim to the provided recipient.
+        let [signer_info, authority_info, board_info, miner_info, recipient_info, system_program, ore_program] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        recipient_info.has_address(&Pubkey::new_from_array(args.recipient))?;
+        claim_sol(
+            signer_info,
+            authority_info,
+            board_info,
+            miner_info,
+            recipient_info,
+            system_program,
+            ore_program,
+        )
+    } else {
+        // Claim to the authority.
+        let [signer_info, board_info, miner_info, system_program, ore_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        claim_sol(
+            signer_info,
+            signer_info,
+            board_info,
+            miner_info,
+            signer_info,
+            system_program,
+            ore_program,
+        )
+    }
+}
+
+fn claim_sol<'a>(
+    signer_info: &AccountInfo<'a>,
+    authority_info: &AccountInfo<'a>,
+    board_info: &AccountInfo<'a>,
+    miner_info: &AccountInfo<'a>,
+    recipient_info: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    ore_program: &AccountInfo<'a>,
+) -> ProgramResult {
     // Load accounts.
     let clock = Clock::get()?;
-    let [signer_info, board_info, miner_info, system_program, ore_program] = accounts else {
-        return Err(ProgramError::NotEnoughAccountKeys);
-    };
     signer_info.is_signer()?;
     board_info.has_address(&BOARD_ADDRESS)?;
+    miner_info.has_seeds(&[MINER, &authority_info.key.to_bytes()], &ore_api::ID)?;
+    migrate_miner(miner_info, signer_info)?;
     let miner = miner_info
-        .has_seeds(&[MINER, &signer_info.key.to_bytes()], &ore_api::ID)?
         .as_account_mut::<Miner>(&ore_api::ID)?
-        .assert_mut(|m| m.authority == *signer_info.key)?;
+        .assert_mut(|m| m.authority == *authority_info.key)?
+        .assert_mut_err(
+            |m| m.can_claim(signer_info.key, recipient_info.key),
+            OreError::NotAuthorized.into(),
+        )?;
+    recipient_info.is_writable()?;
     system_program.is_program(&system_program::ID)?;
     ore_program.is_program(&ore_api::ID)?;
 
@@ -25,7 +73,7 @@ pub fn process_claim_sol(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramR
     sol_log(&format!("Claiming {} SOL", lamports_to_sol(amount)).as_str());
 
     // Transfer reward to recipient.
-    miner_info.send(amount, signer_info);
+    miner_info.send(amount, recipient_info);
 
     // Emit claim event.
     program_log(
@@ -2,6 +2,8 @@ use ore_api::prelude::*;
 use solana_program::rent::Rent;
 use steel::*;
 
+use crate::migrate_round;
+
 /// Closes a round accound, and returns the rent to the rent payer.
 pub fn process_close(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
     // Load accounts.
@@ -16,6 +18,7 @@ pub fn process_close(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResul
         .has_address(&BOARD_ADDRESS)?
         .as_account_mut::<Board>(&ore_api::ID)?;
     rent_payer_info.is_writable()?;
+    migrate_round(round_info, signer_info)?;
     round_info
         .as_account_mut::<Round>(&ore_api::ID)?
         .assert_mut(|r| r.id < board.round_id)?
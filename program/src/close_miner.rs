@@ -0,0 +1,54 @@
+use ore_api::prelude::*;
+use solana_program::{log::sol_log, native_token::lamports_to_sol};
+use steel::*;
+
+use crate::migrate_miner;
+
+/// Closes a miner account, and returns the rent and unused checkpoint fee to the authority.
+pub fn process_close_miner(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let [signer_info, automation_info, miner_info, treasury_info, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    automation_info
+        .is_empty()?
+        .has_seeds(&[AUTOMATION, &signer_info.key.to_bytes()], &ore_api::ID)?;
+    miner_info
+        .is_writable()?
+        .has_seeds(&[MINER, &signer_info.key.to_bytes()], &ore_api::ID)?;
+    migrate_miner(miner_info, signer_info)?;
+    let miner = miner_info
+        .as_account_mut::<Miner>(&ore_api::ID)?
+        .assert_mut_err(
+            |m| m.authority == *signer_info.key,
+            OreError::NotAuthorized.into(),
+        )?;
+    let treasury = treasury_info
+        .has_address(&TREASURY_ADDRESS)?
+        .as_account::<Treasury>(&ore_api::ID)?;
+    system_program.is_program(&system_program::ID)?;
+
+    // Settle rewards.
+    miner.update_rewards(treasury);
+
+    // Miner must have no live automations, and be fully checkpointed and claimed.
+    if miner.automation_count > 0
+        || miner.checkpoint_id != miner.round_id
+        || miner.rewards_sol > 0
+        || miner.rewards_ore > 0
+        || miner.refined_ore > 0
+    {
+        return Err(OreError::InvalidCloseMiner.into());
+    }
+
+    sol_log(&format!(
+        "Closing miner, returning {} SOL",
+        lamports_to_sol(miner_info.lamports())
+    ));
+
+    // Close the account.
+    miner_info.close(signer_info)?;
+
+    Ok(())
+}
@@ -1,35 +1,58 @@
 use entropy_api::state::Var;
 use ore_api::prelude::*;
-use solana_program::{keccak::hashv, log::sol_log, native_token::lamports_to_sol};
+use solana_program::{log::sol_log, native_token::lamports_to_sol};
 use steel::*;
 
+use crate::{close_automation, log_automation, migrate_automation, migrate_miner, migrate_round};
+
 /// Deploys capital to prospect on a square.
 pub fn process_deploy(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
     // Parse data.
-    let args = Deploy::try_from_bytes(data)?;
-    let mut amount = u64::from_le_bytes(args.amount);
-    let mask = u32::from_le_bytes(args.squares);
+    let mut amounts = [0u64; 25];
+    let mut top_up = false;
+    if let Ok(args) = DeployV2::try_from_bytes(data) {
+        amounts = args.amounts.map(u64::from_le_bytes);
+        top_up = args.top_up > 0;
+    } else {
+        // Legacy payload deploys the same amount on every square in the mask.
+        let args = Deploy::try_from_bytes(data)?;
+        let amount = u64::from_le_bytes(args.amount);
+        let mask = u32::from_le_bytes(args.squares);
+        for (i, square_amount) in amounts.iter_mut().enumerate() {
+            if (mask & (1 << i)) != 0 {
+                *square_amount = amount;
+            }
+        }
+    }
+
     // Load accounts.
     let clock = Clock::get()?;
-    let (ore_accounts, entropy_accounts) = accounts.split_at(10);
+    let (ore_accounts, trailing_accounts) = accounts.split_at(10);
     sol_log(&format!("Ore accounts: {:?}", ore_accounts.len()).to_string());
-    sol_log(&format!("Entropy accounts: {:?}", entropy_accounts.len()).to_string());
-    let [signer_info, authority_info, automation_info, board_info, _config_info, miner_info, round_info, treasury_info, system_program, ore_program] =
+    sol_log(&format!("Entropy accounts: {:?}", trailing_accounts.len()).to_string());
+    let [signer_info, authority_info, automation_info, board_info, config_info, miner_info, round_info, treasury_info, system_program, ore_program] =
         ore_accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
+
+    // The signer's executor registry entry may precede the entropy accounts.
+    let (executor_registry_info, entropy_accounts) = match trailing_accounts.split_first() {
+        Some((first, rest)) if *first.key == executor_pda(*signer_info.key).0 => {
+            (Some(first), rest)
+        }
+        _ => (None, trailing_accounts),
+    };
     signer_info.is_signer()?;
     authority_info.is_writable()?;
-    automation_info
-        .is_writable()?
-        .has_seeds(&[AUTOMATION, &authority_info.key.to_bytes()], &ore_api::ID)?;
+    automation_info.is_writable()?;
     let board = board_info
         .has_address(&BOARD_ADDRESS)?
         .as_account_mut::<Board>(&ore_api::ID)?
         .assert_mut(|b| clock.slot >= b.start_slot && clock.slot < b.end_slot)?;
+    round_info.has_seeds(&[ROUND, &board.round_id.to_le_bytes()], &ore_api::ID)?;
+    migrate_round(round_info, signer_info)?;
     let round = round_info
-        .has_seeds(&[ROUND, &board.round_id.to_le_bytes()], &ore_api::ID)?
         .as_account_mut::<Round>(&ore_api::ID)?
         .assert_mut(|r| r.id == board.round_id)?;
     let treasury = treasury_info
@@ -43,22 +66,27 @@ pub fn process_deploy(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResul
     // Wait until first deploy to start round.
     if board.end_slot == u64::MAX {
         board.start_slot = clock.slot;
-        board.end_slot = board.start_slot + ROUND_SLOTS;
+        board.end_slot = board.start_slot + round.round_slots;
         round.expires_at = board.end_slot + ONE_DAY_SLOTS;
 
         // Bump var to the next value.
         let [var_info, entropy_program] = entropy_accounts else {
             return Err(ProgramError::NotEnoughAccountKeys);
         };
+        let config = config_info
+            .has_address(&CONFIG_ADDRESS)?
+            .as_account::<Config>(&ore_api::ID)?;
         var_info
-            .has_address(&VAR_ADDRESS)?
-            .as_account::<Var>(&entropy_api::ID)?
+            .has_address(&config.entropy_var())?
+            .as_account::<Var>(&config.entropy_program())?
             .assert(|v| v.authority == *board_info.key)?;
-        entropy_program.is_program(&entropy_api::ID)?;
+        entropy_program.is_program(&config.entropy_program())?;
 
         // Bump var to the next value.
+        let mut next_ix = entropy_api::sdk::next(*board_info.key, *var_info.key, board.end_slot);
+        next_ix.program_id = *entropy_program.key;
         invoke_signed(
-            &entropy_api::sdk::next(*board_info.key, *var_info.key, board.end_slot),
+            &next_ix,
             &[board_info.clone(), var_info.clone()],
             &entropy_api::ID,
             &[BOARD],
@@ -68,146 +96,120 @@ pub fn process_deploy(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResul
     // Get the automation.
     let mut strategy = u64::MAX;
     let automation = if !automation_info.data_is_empty() {
+        migrate_automation(automation_info, signer_info)?;
         let automation = automation_info
             .as_account_mut::<Automation>(&ore_api::ID)?
             .assert_mut(|a| a.executor == *signer_info.key || a.executor == EXECUTOR_ADDRESS)?
             .assert_mut(|a| a.authority == *authority_info.key)?;
+        automation_info.has_address(&automation.pda().0)?;
+
+        // Close automation if any of its stop conditions have been met.
+        if let Some(reason) = automation.stop_reason(clock.slot, board.round_id) {
+            sol_log(&format!("Stopping automation: {}", reason.as_str()));
+            log_automation(
+                &[board_info.clone(), ore_program.clone()],
+                automation,
+                AutomationEventType::ClosedStopCondition,
+                automation_info.lamports(),
+                clock.unix_timestamp,
+            )?;
+            migrate_miner(miner_info, signer_info)?;
+            let miner = miner_info
+                .as_account_mut::<Miner>(&ore_api::ID)?
+                .assert_mut(|m| m.authority == automation.authority)?;
+            close_automation(automation_info, automation.id, miner, authority_info)?;
+            return Ok(());
+        }
 
         // Conditional deploy.
-        let max_motherlode = automation.conditions.max_motherlode as u64 * ONE_ORE;
-        let min_motherlode = automation.conditions.min_motherlode as u64 * ONE_ORE;
-        if treasury.motherlode > max_motherlode || treasury.motherlode < min_motherlode {
+        if let Some(reason) = automation
+            .conditions
+            .skip_reason(treasury.motherlode, board.production_cost_ema)
+        {
+            sol_log(&format!("Skipping deploy: {}", reason.as_str()));
             return Ok(());
         }
 
+        // Require a registered, unpaused executor whose fee ceiling covers the fee, if the automation requires it.
+        if automation.require_registered_executor > 0 {
+            let Some(executor_registry_info) = executor_registry_info else {
+                return Err(OreError::InvalidExecutor.into());
+            };
+            if executor_registry_info.data_is_empty() {
+                return Err(OreError::InvalidExecutor.into());
+            }
+            let executor = executor_registry_info.as_account::<Executor>(&ore_api::ID)?;
+            if executor.paused > 0 {
+                return Err(OreError::ExecutorPaused.into());
+            }
+            if automation.fee
+                > executor.max_fee_for(AutomationStrategy::from_u64(automation.strategy))
+            {
+                return Err(OreError::FeeRateTooHigh.into());
+            }
+        }
+
         // Set strategy.
         strategy = automation.strategy as u64;
         Some(automation)
     } else {
+        automation_info.has_seeds(&[AUTOMATION, &authority_info.key.to_bytes()], &ore_api::ID)?;
         None
     };
 
-    // Update amount and mask for automation.
-    let mut squares = [false; 25];
+    // Update amounts for automation.
     if let Some(automation) = &automation {
-        // Set amount and squares based on automation strategy.
+        // Set amounts based on automation strategy.
         match AutomationStrategy::from_u64(automation.strategy as u64) {
             AutomationStrategy::Preferred => {
                 // Preferred automation strategy. Use the miner authority's provided mask.
-                amount = automation.amount;
-                for i in 0..25 {
-                    squares[i] = (automation.mask & (1 << i)) != 0;
+                top_up = false;
+                for (i, square_amount) in amounts.iter_mut().enumerate() {
+                    *square_amount = if (automation.mask & (1 << i)) != 0 {
+                        automation.amount
+                    } else {
+                        0
+                    };
                 }
             }
             AutomationStrategy::Random => {
-                // Random automation strategy. Generate a random mask based on number of squares user wants to deploy to.
-                amount = automation.amount;
-
-                // If first deploy, use the mask provided by the user.
-                if automation.total_sol_spent == 0 {
-                    for i in 0..25 {
-                        squares[i] = (automation.mask & (1 << i)) != 0;
-                    }
-                } else if automation.conditions.solo_tiles > 0
-                    || automation.conditions.split_tiles > 0
-                {
-                    // User has a preferred solo / split strategy. Generate a mask based on this preferrence.
-
-                    // First generate the solo / split mask.
-                    let distribution_mask = round.distribution_mask();
-
-                    // Build squares array based on user's preferred solo and split tiles using fixed-size arrays.
-
-                    // First collect the indices of solos and splits (maximum 25 of each).
-                    let mut solo_idxs = [0usize; 25];
-                    let mut split_idxs = [0usize; 25];
-                    let mut num_solo = 0usize;
-                    let mut num_split = 0usize;
-                    for i in 0..25 {
-                        if (distribution_mask & (1 << i)) != 0 {
-                            solo_idxs[num_solo] = i;
-                            num_solo += 1;
-                        } else {
-                            split_idxs[num_split] = i;
-                            num_split += 1;
-                        }
-                    }
-
-                    let solo_pref = automation.conditions.solo_tiles as usize;
-                    let split_pref = automation.conditions.split_tiles as usize;
-
-                    // Build a random seed using user and round id for deterministic shuffle
-                    let mut seed = [0u8; 32];
-                    let h = hashv(&[
-                        &automation.authority.to_bytes(),
-                        &round.id.to_le_bytes(),
-                        b"solo_split_mask",
-                    ])
-                    .0;
-                    seed.copy_from_slice(&h);
-
-                    // Deterministic shuffle for solo and split index arrays in place
-                    fn deterministic_shuffle(idxs: &mut [usize], len: usize, seed: &[u8; 32]) {
-                        let mut local_seed = [0u8; 8];
-                        for i in (1..len).rev() {
-                            // Use 8 bytes from the 32-byte seed cycling per index
-                            let start = (i % (32 - 8 + 1)) as usize;
-                            local_seed.copy_from_slice(&seed[start..start + 8]);
-                            let hashval = u64::from_le_bytes(local_seed);
-                            let j = (hashval % (i as u64 + 1)) as usize;
-                            // Swap i <-> j
-                            let tmp = idxs[i];
-                            idxs[i] = idxs[j];
-                            idxs[j] = tmp;
-                        }
-                    }
-
-                    // Apply shuffle to both solo and split slice parts
-                    let (solo_idxs_slice, split_idxs_slice) =
-                        (&mut solo_idxs[..num_solo], &mut split_idxs[..num_split]);
-                    deterministic_shuffle(solo_idxs_slice, num_solo, &seed);
-                    deterministic_shuffle(split_idxs_slice, num_split, &seed);
-
-                    // Prepare the tiles to select
-                    let num_solo_to_select = num_solo.min(solo_pref);
-                    let num_split_to_select = num_split.min(split_pref);
-
-                    // Clear current squares
-                    for i in 0..25 {
-                        squares[i] = false;
-                    }
-
-                    // Set true on chosen solo tiles
-                    for n in 0..num_solo_to_select {
-                        squares[solo_idxs[n]] = true;
-                    }
-
-                    // Set true on chosen split tiles
-                    for n in 0..num_split_to_select {
-                        squares[split_idxs[n]] = true;
-                    }
-                } else {
-                    // If not first deploy and no preferred solo / split strategy, generate a random mask based on number of squares user wants to deploy to.
-                    let num_squares = (0..25)
-                        .filter(|i| (automation.mask & (1 << i)) != 0)
-                        .count() as u64;
-                    let r = hashv(&[&automation.authority.to_bytes(), &round.id.to_le_bytes()]).0;
-                    squares = generate_random_mask(num_squares, &r);
+                // Random automation strategy. Select squares pseudo-randomly, seeded by the authority and round.
+                top_up = false;
+                let squares = random_squares(automation, round);
+                for (square_amount, &selected) in amounts.iter_mut().zip(squares.iter()) {
+                    *square_amount = if selected { automation.amount } else { 0 };
+                }
+            }
+            AutomationStrategy::ExpectedValue => {
+                // Expected value automation strategy. Deploy to the least crowded, or best returning, squares in the mask.
+                top_up = false;
+                let squares = expected_value_squares(
+                    automation,
+                    round,
+                    board.mass(automation.amount, clock.slot),
+                    board.production_cost_ema,
+                    treasury.motherlode,
+                );
+                for (square_amount, &selected) in amounts.iter_mut().zip(squares.iter()) {
+                    *square_amount = if selected { automation.amount } else { 0 };
                 }
             }
             AutomationStrategy::Discretionary | AutomationStrategy::DiscretionaryBps => {
-                // Discretionary automation strategy. Use the executor's provided mask.
-                amount = amount.min(automation.amount);
-                for i in 0..25 {
-                    squares[i] = (mask & (1 << i)) != 0;
+                // Discretionary automation strategy. Use the executor's provided amounts, capped per square.
+                for square_amount in amounts.iter_mut() {
+                    *square_amount = (*square_amount).min(automation.amount);
                 }
             }
         }
-    } else {
-        // Convert provided 32-bit mask into array of 25 booleans, where each bit in the mask
-        // determines if that square index is selected (true) or not (false)
-        for i in 0..25 {
-            squares[i] = (mask & (1 << i)) != 0;
+
+        // Skip if the deploy would exceed the automation's daily budget.
+        let total_deploy = amounts.iter().sum::<u64>();
+        if automation.exceeds_daily_budget(total_deploy, clock.unix_timestamp) {
+            sol_log(&format!(
+                "Skipping deploy: {}",
+                AutomationSkipReason::DailyBudgetExceeded.as_str()
+            ));
+            return Ok(());
         }
     }
 
@@ -223,7 +225,10 @@ pub fn process_deploy(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResul
         let miner = miner_info.as_account_mut::<Miner>(&ore_api::ID)?;
         miner.authority = *signer_info.key;
         miner.deployed = [0; 25];
+        miner.mass = [0; 25];
         miner.cumulative = [0; 25];
+        miner.topup_cumulative = [0; 25];
+        miner.topup = [0; 25];
         miner.rewards_sol = 0;
         miner.rewards_ore = 0;
         miner.round_id = 0;
@@ -233,6 +238,7 @@ pub fn process_deploy(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResul
         miner.auto_return = 1;
         miner
     } else {
+        migrate_miner(miner_info, signer_info)?;
         miner_info
             .as_account_mut::<Miner>(&ore_api::ID)?
             .assert_mut(|m| {
@@ -247,74 +253,123 @@ pub fn process_deploy(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResul
     // Reset miner
     if miner.round_id != round.id {
         // Assert miner has checkpointed prior round.
-        assert!(
-            miner.checkpoint_id == miner.round_id,
-            "Miner has not checkpointed"
-        );
+        if miner.checkpoint_id != miner.round_id {
+            return Err(OreError::MinerNotCheckpointed.into());
+        }
 
         // Reset miner for new round.
         miner.deployed = [0; 25];
-        miner.cumulative = round.deployed;
+        miner.mass = [0; 25];
+        miner.cumulative = *round.weights();
+        miner.topup_cumulative = [0; 25];
+        miner.topup = [0; 25];
+        miner.topup_deployed = [0; 25];
         miner.round_id = round.id;
     }
 
+    // Manual and automation deploys, or deploys from different automations, may not mix within a round.
+    match &automation {
+        Some(automation) => {
+            if miner.is_funded_by_other(round.id, automation.id) {
+                sol_log(&format!(
+                    "Skipping deploy: {}",
+                    AutomationSkipReason::FundedElsewhere.as_str()
+                ));
+                return Ok(());
+            }
+        }
+        None => {
+            if miner.is_funded_by_other(round.id, MANUAL_AUTOMATION_ID) {
+                return Err(OreError::AutomationConflict.into());
+            }
+        }
+    }
+
     // Update total miners for round.
     let is_first_deploy = miner.deployed.iter().sum::<u64>() == 0;
 
-    // Close automation if it doesn't have enough balance to cover all requested squares.
-    if is_first_deploy {
-        if let Some(automation) = &automation {
-            let required_squares = squares.iter().filter(|&&s| s).count() as u64;
-            let total_deploy = amount * required_squares;
-            let estimated_fee = automation.min_fee(total_deploy);
-            if automation.balance < total_deploy + estimated_fee {
-                automation_info.send(estimated_fee, &signer_info);
-                automation_info.close(authority_info)?;
-                return Ok(());
-            }
+    if let Some(automation) = &automation {
+        // Automations may not deploy more than their per-square amount, including top-ups.
+        for (square_id, square_amount) in amounts.iter_mut().enumerate() {
+            *square_amount = if miner.deployed[square_id] > 0 && !top_up {
+                0
+            } else {
+                (*square_amount).min(automation.amount.saturating_sub(miner.deployed[square_id]))
+            };
+        }
+
+        // Close automation if it doesn't have enough balance to cover all requested squares.
+        let total_deploy = amounts.iter().sum::<u64>();
+        let estimated_fee = automation.deploy_fee(total_deploy, round.id);
+        if automation.balance < total_deploy + estimated_fee {
+            automation_info.send(estimated_fee, &signer_info);
+            log_automation(
+                &[board_info.clone(), ore_program.clone()],
+                automation,
+                AutomationEventType::ClosedInsufficientBalance,
+                automation_info.lamports(),
+                clock.unix_timestamp,
+            )?;
+            close_automation(automation_info, automation.id, miner, authority_info)?;
+            return Ok(());
         }
     }
 
     // Calculate all deployments.
     let mut total_amount = 0;
     let mut total_squares = 0;
-    let mut deployed_squares = [false; 25];
-    for (square_id, &should_deploy) in squares.iter().enumerate() {
-        // Skip if square index is out of bounds.
-        if square_id > 24 {
-            break;
-        }
-
+    let mut deployed_amounts = [0; 25];
+    for (square_id, &amount) in amounts.iter().enumerate() {
         // Skip if square is not deployed to.
-        if !should_deploy {
+        if amount == 0 {
             continue;
         }
 
-        // Skip if miner already deployed to this square.
+        // Weight the deployment by the time remaining in the round.
+        let mass = board.mass(amount, clock.slot);
+
         if miner.deployed[square_id] > 0 {
-            continue;
-        }
+            // Skip if miner already deployed to this square, unless topping up.
+            if !top_up {
+                continue;
+            }
 
-        // Record cumulative amount.
-        miner.cumulative[square_id] = round.deployed[square_id];
+            // Record the top-up range, skipping if the miner already holds two ranges on this square.
+            let weight = if round.is_mass_weighted() {
+                mass
+            } else {
+                amount
+            };
+            if !miner.record_topup(round, square_id, amount, weight) {
+                sol_log(&format!("Skipping top-up on square {}", square_id));
+                continue;
+            }
+        } else {
+            // Record cumulative weight.
+            miner.cumulative[square_id] = round.weights()[square_id];
+            round.count[square_id] += 1;
+        }
 
         // Update miner
-        miner.deployed[square_id] = amount;
+        miner.deployed[square_id] += amount;
+        miner.mass[square_id] += mass;
 
         // Update board
         round.deployed[square_id] += amount;
+        round.mass[square_id] += mass;
         // round.total_deployed += amount;
-        round.count[square_id] += 1;
 
         // Update totals.
         total_amount += amount;
         total_squares += 1;
-        deployed_squares[square_id] = true;
+        deployed_amounts[square_id] = amount;
     }
 
     // Update total miners for round.
+    // Record which automation funded the round, so checkpoints reload winnings into it.
     if is_first_deploy && total_amount > 0 {
         round.total_miners += 1;
+        miner.automation_id = automation.as_ref().map_or(MANUAL_AUTOMATION_ID, |a| a.id);
     }
 
     // Increment miner lifetime deployed.
@@ -331,12 +386,15 @@ pub fn process_deploy(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResul
         // Update automation total sol spent.
         automation.total_sol_spent += total_amount;
 
-        // Calculate automation fee.
-        let automation_fee = if is_first_deploy && total_amount > 0 {
-            automation.min_fee(total_amount)
-        } else {
-            0
-        };
+        // Calculate automation fee, charged on the automation's first deploy of the round.
+        let automation_fee = automation.deploy_fee(total_amount, round.id);
+
+        // Record the deploy against the automation's limits.
+        let is_new_round = total_amount > 0 && automation.is_new_round(round.id);
+        automation.record_deploy(total_amount, is_new_round, clock.unix_timestamp);
+        if is_new_round {
+            automation.last_round_id = round.id;
+        }
 
         // Update automation balance.
         automation.balance -= total_amount + automation_fee;
@@ -344,8 +402,15 @@ pub fn process_deploy(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResul
         automation_info.send(automation_fee, &signer_info);
 
         // Close automation if balance is less than what's required to deploy 1 square.
-        if automation.balance < automation.amount + automation.min_fee(automation.amount) {
-            automation_info.close(authority_info)?;
+        if automation.balance < automation.min_balance() {
+            log_automation(
+                &[board_info.clone(), ore_program.clone()],
+                automation,
+                AutomationEventType::ClosedInsufficientBalance,
+                automation_info.lamports(),
+                clock.unix_timestamp,
+            )?;
+            close_automation(automation_info, automation.id, miner, authority_info)?;
         }
     } else {
         round_info.collect(total_amount, &signer_info)?;
@@ -353,12 +418,20 @@ pub fn process_deploy(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResul
 
     // Rebuild the mask from the deployed squares.
     let mut deployed_mask = 0;
-    for (square_id, &deployed) in deployed_squares.iter().enumerate() {
-        if deployed {
+    for (square_id, &deployed) in deployed_amounts.iter().enumerate() {
+        if deployed > 0 {
             deployed_mask |= 1 << square_id;
         }
     }
 
+    // Report a per-square amount only if it was the same on every deployed square.
+    let amount = deployed_amounts
+        .iter()
+        .find(|&&a| a > 0)
+        .copied()
+        .filter(|&a| deployed_amounts.iter().all(|&d| d == 0 || d == a))
+        .unwrap_or(0);
+
     // Log the deploy event.
     program_log(
         &[board_info.clone(), ore_program.clone()],
@@ -372,6 +445,8 @@ pub fn process_deploy(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResul
             strategy,
             total_squares,
             ts: clock.unix_timestamp,
+            total_amount,
+            amounts: deployed_amounts,
         }
         .to_bytes(),
     )?;
@@ -381,7 +456,7 @@ pub fn process_deploy(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResul
         &format!(
             "Round #{}: deploying {} SOL to {} squares",
             round.id,
-            lamports_to_sol(amount),
+            lamports_to_sol(total_amount),
             total_squares,
         )
         .as_str(),
@@ -389,20 +464,3 @@ pub fn process_deploy(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResul
 
     Ok(())
 }
-
-fn generate_random_mask(num_squares: u64, r: &[u8]) -> [bool; 25] {
-    let mut new_mask = [false; 25];
-    let mut selected = 0;
-    for i in 0..25 {
-        let rand_byte = r[i];
-        let remaining_needed = num_squares as u64 - selected as u64;
-        let remaining_positions = 25 - i;
-        if remaining_needed > 0
-            && (rand_byte as u64) * (remaining_positions as u64) < (remaining_needed * 256)
-        {
-            new_mask[i] = true;
-            selected += 1;
-        }
-    }
-    new_mask
-}
@@ -1,29 +1,65 @@
+mod accept_admin;
 mod automate;
 mod bury;
 mod buyback;
 mod checkpoint;
+mod checkpoint_many;
+mod claim_and_bury;
+mod claim_and_stake;
 mod claim_ore;
 mod claim_sol;
 mod close;
+mod close_miner;
 mod deploy;
 mod log;
+mod migrate;
+mod migrate_miner;
 mod new_var;
+mod register_executor;
 mod reset;
 mod set_admin;
+mod set_admin_fee;
+mod set_fee_collector;
+mod set_fee_rate;
+mod set_mass_round;
+mod set_miner_preferences;
+mod set_round_timing;
+mod set_var;
+mod set_withdraw_config;
+mod withdraw;
+mod withdraw_automation;
 mod wrap;
 
+use accept_admin::*;
 use automate::*;
 use bury::*;
 use buyback::*;
 use checkpoint::*;
+use checkpoint_many::*;
+use claim_and_bury::*;
+use claim_and_stake::*;
 use claim_ore::*;
 use claim_sol::*;
 use close::*;
+use close_miner::*;
 use deploy::*;
 use log::*;
+use migrate::*;
+use migrate_miner::*;
 use new_var::*;
+use register_executor::*;
 use reset::*;
 use set_admin::*;
+use set_admin_fee::*;
+use set_fee_collector::*;
+use set_fee_rate::*;
+use set_mass_round::*;
+use set_miner_preferences::*;
+use set_round_timing::*;
+use set_var::*;
+use set_withdraw_config::*;
+use withdraw::*;
+use withdraw_automation::*;
 use wrap::*;
 
 use ore_api::instruction::*;
@@ -41,18 +77,35 @@ pub fn process_instruction(
         // Miner
         OreInstruction::Automate => process_automate(accounts, data)?,
         OreInstruction::Checkpoint => process_checkpoint(accounts, data)?,
+        OreInstruction::CheckpointMany => process_checkpoint_many(accounts, data)?,
         OreInstruction::ClaimSOL => process_claim_sol(accounts, data)?,
         OreInstruction::ClaimORE => process_claim_ore(accounts, data)?,
+        OreInstruction::ClaimAndStake => process_claim_and_stake(accounts, data)?,
+        OreInstruction::ClaimAndBury => process_claim_and_bury(accounts, data)?,
         OreInstruction::Deploy => process_deploy(accounts, data)?,
         OreInstruction::Log => process_log(accounts, data)?,
+        OreInstruction::MigrateMiner => process_migrate_miner(accounts, data)?,
         OreInstruction::Close => process_close(accounts, data)?,
+        OreInstruction::CloseMiner => process_close_miner(accounts, data)?,
+        OreInstruction::RegisterExecutor => process_register_executor(accounts, data)?,
         OreInstruction::Reset => process_reset(accounts, data)?,
+        OreInstruction::SetMinerPreferences => process_set_miner_preferences(accounts, data)?,
+        OreInstruction::Withdraw => process_withdraw(accounts, data)?,
+        OreInstruction::WithdrawAutomation => process_withdraw_automation(accounts, data)?,
 
         // Admin
         OreInstruction::Buyback => process_buyback(accounts, data)?,
         OreInstruction::Bury => process_bury(accounts, data)?,
         OreInstruction::Wrap => process_wrap(accounts, data)?,
         OreInstruction::SetAdmin => process_set_admin(accounts, data)?,
+        OreInstruction::AcceptAdmin => process_accept_admin(accounts, data)?,
+        OreInstruction::SetFeeCollector => process_set_fee_collector(accounts, data)?,
+        OreInstruction::SetFeeRate => process_set_fee_rate(accounts, data)?,
+        OreInstruction::SetAdminFee => process_set_admin_fee(accounts, data)?,
+        OreInstruction::SetRoundTiming => process_set_round_timing(accounts, data)?,
+        OreInstruction::SetVar => process_set_var(accounts, data)?,
+        OreInstruction::SetWithdrawConfig => process_set_withdraw_config(accounts, data)?,
+        OreInstruction::SetMassRound => process_set_mass_round(accounts, data)?,
         OreInstruction::NewVar => process_new_var(accounts, data)?,
         OreInstruction::Liq => return Err(ProgramError::InvalidInstructionData),
     }
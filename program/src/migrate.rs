@@ -0,0 +1,85 @@
+use ore_api::prelude::*;
+use solana_program::rent::Rent;
+use steel::*;
+
+/// Reallocates the config account if it predates the current layout.
+/// New fields are zero-initialized, and the payer covers any additional rent.
+pub fn migrate_config<'info>(
+    config_info: &AccountInfo<'info>,
+    payer_info: &AccountInfo<'info>,
+) -> ProgramResult {
+    if config_info.data_len() >= Config::SIZE {
+        return Ok(());
+    }
+    let required_rent = Rent::get()?.minimum_balance(Config::SIZE);
+    let lamports = config_info.lamports();
+    if required_rent > lamports {
+        config_info.collect(required_rent - lamports, payer_info)?;
+    }
+    config_info.realloc(Config::SIZE, true)?;
+    Ok(())
+}
+
+/// Reallocates a round account if it predates the current layout.
+/// Rounds opened before fee rates and timing were configurable keep the legacy constants.
+pub fn migrate_round<'info>(
+    round_info: &AccountInfo<'info>,
+    payer_info: &AccountInfo<'info>,
+) -> ProgramResult {
+    if round_info.data_is_empty() || round_info.data_len() >= Round::SIZE {
+        return Ok(());
+    }
+    realloc_account(round_info, payer_info, Round::discriminator(), Round::SIZE)?;
+    let round = round_info.as_account_mut::<Round>(&ore_api::ID)?;
+    round.admin_fee_rate = ADMIN_FEE;
+    round.protocol_fee_rate = PROTOCOL_FEE;
+    round.round_slots = ROUND_SLOTS;
+    round.intermission_slots = INTERMISSION_SLOTS;
+    Ok(())
+}
+
+/// Reallocates a miner account if it predates the current layout.
+/// New fields are zero-initialized, and the payer covers any additional rent.
+pub fn migrate_miner<'info>(
+    miner_info: &AccountInfo<'info>,
+    payer_info: &AccountInfo<'info>,
+) -> ProgramResult {
+    if miner_info.data_is_empty() || miner_info.data_len() >= Miner::SIZE {
+        return Ok(());
+    }
+    realloc_account(miner_info, payer_info, Miner::discriminator(), Miner::SIZE)
+}
+
+/// Reallocates an automation account if it predates the current layout.
+/// New fields are zero-initialized, which leaves every stop condition disabled.
+pub fn migrate_automation<'info>(
+    automation_info: &AccountInfo<'info>,
+    payer_info: &AccountInfo<'info>,
+) -> ProgramResult {
+    if automation_info.data_is_empty() || automation_info.data_len() >= Automation::SIZE {
+        return Ok(());
+    }
+    realloc_account(
+        automation_info,
+        payer_info,
+        Automation::discriminator(),
+        Automation::SIZE,
+    )
+}
+
+fn realloc_account<'info>(
+    account_info: &AccountInfo<'info>,
+    payer_info: &AccountInfo<'info>,
+    discriminator: u8,
+    size: usize,
+) -> ProgramResult {
+    account_info.is_writable()?.has_owner(&ore_api::ID)?;
+    if account_info.try_borrow_data()?[0] != discriminator {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let required_rent = Rent::get()?.minimum_balance(size);
+    let legacy_rent = Rent::get()?.minimum_balance(account_info.data_len());
+    account_info.collect(required_rent.saturating_sub(legacy_rent), payer_info)?;
+    account_info.realloc(size, true)?;
+    Ok(())
+}
@@ -0,0 +1,121 @@
+use ore_api::prelude::*;
+use solana_program::log::sol_log;
+use steel::*;
+
+use crate::migrate_miner;
+
+/// Moves a miner's claimable balances and lifetime stats to a miner account owned by a new authority.
+/// Automations and claim delegates are tied to the old authority, so they must be removed first.
+pub fn process_migrate_miner(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let [signer_info, new_authority_info, automation_info, miner_info, new_miner_info, treasury_info, system_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    new_authority_info.is_signer()?;
+    if signer_info.key == new_authority_info.key {
+        return Err(ProgramError::InvalidArgument);
+    }
+    automation_info
+        .is_empty()?
+        .has_seeds(&[AUTOMATION, &signer_info.key.to_bytes()], &ore_api::ID)?;
+    miner_info
+        .is_writable()?
+        .has_seeds(&[MINER, &signer_info.key.to_bytes()], &ore_api::ID)?;
+    migrate_miner(miner_info, signer_info)?;
+    let miner = miner_info
+        .as_account_mut::<Miner>(&ore_api::ID)?
+        .assert_mut_err(
+            |m| m.authority == *signer_info.key,
+            OreError::NotAuthorized.into(),
+        )?
+        .assert_mut_err(
+            |m| m.checkpoint_id == m.round_id,
+            OreError::MinerNotCheckpointed.into(),
+        )?
+        .assert_mut_err(
+            |m| m.automation_count == 0 && m.claim_delegate == Pubkey::default(),
+            OreError::InvalidMigrateMiner.into(),
+        )?;
+    new_miner_info
+        .is_writable()?
+        .has_seeds(&[MINER, &new_authority_info.key.to_bytes()], &ore_api::ID)?;
+    let treasury = treasury_info
+        .has_address(&TREASURY_ADDRESS)?
+        .as_account::<Treasury>(&ore_api::ID)?;
+    system_program.is_program(&system_program::ID)?;
+
+    // Open new miner account.
+    let new_miner = if new_miner_info.data_is_empty() {
+        create_program_account::<Miner>(
+            new_miner_info,
+            system_program,
+            signer_info,
+            &ore_api::ID,
+            &[MINER, &new_authority_info.key.to_bytes()],
+        )?;
+        let new_miner = new_miner_info.as_account_mut::<Miner>(&ore_api::ID)?;
+        new_miner.authority = *new_authority_info.key;
+        new_miner.deployed = [0; 25];
+        new_miner.mass = [0; 25];
+        new_miner.cumulative = [0; 25];
+        new_miner.topup_cumulative = [0; 25];
+        new_miner.topup = [0; 25];
+        new_miner.rewards_sol = 0;
+        new_miner.rewards_ore = 0;
+        new_miner.round_id = 0;
+        new_miner.checkpoint_id = 0;
+        new_miner.lifetime_rewards_sol = 0;
+        new_miner.lifetime_rewards_ore = 0;
+        new_miner.auto_return = miner.auto_return;
+        new_miner
+    } else {
+        migrate_miner(new_miner_info, signer_info)?;
+        new_miner_info
+            .as_account_mut::<Miner>(&ore_api::ID)?
+            .assert_mut_err(
+                |m| m.authority == *new_authority_info.key,
+                OreError::NotAuthorized.into(),
+            )?
+            .assert_mut_err(
+                |m| m.checkpoint_id == m.round_id,
+                OreError::MinerNotCheckpointed.into(),
+            )?
+    };
+
+    // Settle rewards on both accounts, so they accrue from the same rewards factor.
+    miner.update_rewards(treasury);
+    new_miner.update_rewards(treasury);
+
+    // Move claimable balances. A checkpoint fee beyond the one the new miner needs becomes claimable SOL.
+    let sol = miner.rewards_sol + miner.checkpoint_fee;
+    new_miner.rewards_sol += miner.rewards_sol;
+    if new_miner.checkpoint_fee == 0 {
+        new_miner.checkpoint_fee = miner.checkpoint_fee;
+    } else {
+        new_miner.rewards_sol += miner.checkpoint_fee;
+    }
+    new_miner.rewards_ore += miner.rewards_ore;
+    new_miner.refined_ore += miner.refined_ore;
+    miner.rewards_sol = 0;
+    miner.checkpoint_fee = 0;
+    miner.rewards_ore = 0;
+    miner.refined_ore = 0;
+
+    // Move lifetime stats.
+    new_miner.lifetime_deployed += miner.lifetime_deployed;
+    new_miner.lifetime_rewards_sol += miner.lifetime_rewards_sol;
+    new_miner.lifetime_rewards_ore += miner.lifetime_rewards_ore;
+    miner.lifetime_deployed = 0;
+    miner.lifetime_rewards_sol = 0;
+    miner.lifetime_rewards_ore = 0;
+
+    // Transfer SOL held for the miner.
+    miner_info.send(sol, new_miner_info);
+
+    sol_log(&format!("Migrated miner to {}", new_authority_info.key));
+
+    Ok(())
+}
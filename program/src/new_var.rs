@@ -1,6 +1,8 @@
 use ore_api::prelude::*;
 use steel::*;
 
+use crate::migrate_config;
+
 /// Creates a new var account.
 pub fn process_new_var(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
     // Parse data.
@@ -19,8 +21,9 @@ pub fn process_new_var(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResu
     let board = board_info
         .has_address(&BOARD_ADDRESS)?
         .as_account_mut::<Board>(&ore_api::ID)?;
+    config_info.has_address(&CONFIG_ADDRESS)?;
+    migrate_config(config_info, signer_info)?;
     config_info
-        .has_address(&CONFIG_ADDRESS)?
         .as_account_mut::<Config>(&ore_api::ID)?
         .assert_mut_err(
             |c| c.protocol.authority == *signer_info.key,
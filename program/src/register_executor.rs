@@ -0,0 +1,83 @@
+use ore_api::prelude::*;
+use ore_api::sdk::program_log;
+use steel::*;
+
+/// Registers or updates an executor in the registry.
+pub fn process_register_executor(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = RegisterExecutor::try_from_bytes(data)?;
+    let max_fee = u64::from_le_bytes(args.max_fee);
+    let max_bps = u64::from_le_bytes(args.max_bps);
+    let strategies = args.strategies as u64;
+    let paused = args.paused > 0;
+
+    // Validate fee ceilings and supported strategies.
+    if max_bps > 100 {
+        return Err(OreError::FeeRateTooHigh.into());
+    }
+    if strategies >> (AutomationStrategy::ExpectedValue as u64 + 1) != 0 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    // Load accounts.
+    let clock = Clock::get()?;
+    let [signer_info, executor_info, board_info, system_program, ore_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    executor_info
+        .is_writable()?
+        .has_seeds(&[EXECUTOR, &signer_info.key.to_bytes()], &ore_api::ID)?;
+    board_info.has_address(&BOARD_ADDRESS)?;
+    system_program.is_program(&system_program::ID)?;
+    ore_program.is_program(&ore_api::ID)?;
+
+    // Create executor.
+    let is_new = executor_info.data_is_empty();
+    let executor = if is_new {
+        create_program_account::<Executor>(
+            executor_info,
+            system_program,
+            signer_info,
+            &ore_api::ID,
+            &[EXECUTOR, &signer_info.key.to_bytes()],
+        )?;
+        let executor = executor_info.as_account_mut::<Executor>(&ore_api::ID)?;
+        executor.authority = *signer_info.key;
+        executor
+    } else {
+        executor_info
+            .as_account_mut::<Executor>(&ore_api::ID)?
+            .assert_mut_err(
+                |e| e.authority == *signer_info.key,
+                OreError::NotAuthorized.into(),
+            )?
+    };
+
+    // Update registry entry.
+    executor.name_hash = args.name_hash;
+    executor.max_fee = max_fee;
+    executor.max_bps = max_bps;
+    executor.strategies = strategies;
+    executor.paused = paused as u64;
+    executor.uri = args.uri;
+
+    // Emit event.
+    program_log(
+        &[board_info.clone(), ore_program.clone()],
+        ExecutorEvent {
+            disc: 9,
+            authority: executor.authority,
+            event_type: if is_new { 0 } else { 1 },
+            name_hash: executor.name_hash,
+            max_fee: executor.max_fee,
+            max_bps: executor.max_bps,
+            strategies: executor.strategies,
+            paused: executor.paused,
+            ts: clock.unix_timestamp,
+        }
+        .to_bytes(),
+    )?;
+
+    Ok(())
+}
@@ -4,7 +4,7 @@ use ore_mint_api::consts::MAX_SUPPLY;
 use solana_program::{keccak, log::sol_log};
 use steel::*;
 
-// TODO Integrate admin fee
+use crate::{migrate_config, migrate_round};
 
 /// Pays out the winners and block reward.
 pub fn process_reset(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
@@ -13,7 +13,7 @@ pub fn process_reset(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResul
     let (ore_accounts, other_accounts) = accounts.split_at(14);
     sol_log(&format!("Ore accounts: {:?}", ore_accounts.len()).to_string());
     sol_log(&format!("Other accounts: {:?}", other_accounts.len()).to_string());
-    let [signer_info, board_info, _config_info, fee_collector_info, mint_info, round_info, round_next_info, top_miner_info, treasury_info, treasury_tokens_info, system_program, token_program, ore_program, slot_hashes_sysvar] =
+    let [signer_info, board_info, config_info, fee_collector_info, mint_info, round_info, round_next_info, top_miner_info, treasury_info, treasury_tokens_info, system_program, token_program, ore_program, slot_hashes_sysvar] =
         ore_accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
@@ -21,14 +21,18 @@ pub fn process_reset(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResul
     signer_info.is_signer()?;
     let board = board_info
         .has_address(&BOARD_ADDRESS)?
-        .as_account_mut::<Board>(&ore_api::ID)?
-        .assert_mut(|b| clock.slot >= b.end_slot + INTERMISSION_SLOTS)?;
+        .as_account_mut::<Board>(&ore_api::ID)?;
+    config_info.is_writable()?.has_address(&CONFIG_ADDRESS)?;
+    migrate_config(config_info, signer_info)?;
+    let config = config_info.as_account::<Config>(&ore_api::ID)?;
     fee_collector_info
         .is_writable()?
-        .has_address(&ADMIN_FEE_COLLECTOR)?;
+        .has_address(&config.fee_collector())?;
+    migrate_round(round_info, signer_info)?;
     let round = round_info
         .as_account_mut::<Round>(&ore_api::ID)?
-        .assert_mut(|r| r.id == board.round_id)?;
+        .assert_mut(|r| r.id == board.round_id)?
+        .assert_mut(|r| clock.slot >= board.end_slot.saturating_add(r.intermission_slots))?;
     round_next_info
         .is_empty()?
         .is_writable()?
@@ -54,6 +58,7 @@ pub fn process_reset(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResul
     let round_next = round_next_info.as_account_mut::<Round>(&ore_api::ID)?;
     round_next.id = board.round_id + 1;
     round_next.deployed = [0; 25];
+    round_next.mass = [0; 25];
     round_next.slot_hash = [0; 32];
     round_next.count = [0; 25];
     round_next.expires_at = u64::MAX; // Set to max, to indicate round is waiting for first deploy to begin.
@@ -64,6 +69,11 @@ pub fn process_reset(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResul
     round_next.total_vaulted = 0;
     round_next.total_returned_sol = 0;
     round_next.total_miners = 0;
+    round_next.admin_fee_rate = config.admin_fee_rate();
+    round_next.protocol_fee_rate = config.protocol_fee_rate();
+    round_next.round_slots = config.round_slots();
+    round_next.intermission_slots = config.intermission_slots();
+    round_next.mass_weighted = config.is_mass_round(round_next.id) as u64;
 
     // Sample random variable
     let (entropy_accounts, mint_accounts) = other_accounts.split_at(2);
@@ -73,13 +83,13 @@ pub fn process_reset(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResul
         return Err(ProgramError::NotEnoughAccountKeys);
     };
     let var = var_info
-        .has_address(&VAR_ADDRESS)?
-        .as_account::<Var>(&entropy_api::ID)?
+        .has_address(&config.entropy_var())?
+        .as_account::<Var>(&config.entropy_program())?
         .assert(|v| v.authority == *board_info.key)?
         .assert(|v| v.slot_hash != [0; 32])?
         .assert(|v| v.seed != [0; 32])?
         .assert(|v| v.value != [0; 32])?;
-    entropy_program.is_program(&entropy_api::ID)?;
+    entropy_program.is_program(&config.entropy_program())?;
 
     // Print the seed and slot hash.
     let seed = keccak::Hash::new_from_array(var.seed);
@@ -98,6 +108,7 @@ pub fn process_reset(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResul
         round.total_vaulted = 0;
         round.total_returned_sol = round.total_deployed();
         round.deployed = [0; 25];
+        round.mass = [0; 25];
 
         // Emit event.
         program_log(
@@ -189,20 +200,18 @@ pub fn process_reset(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResul
                 sol_log(&format!("Top miner sample: {}", top_miner_sample).to_string());
                 sol_log(&format!("Miner cumulative: {:?}", miner.cumulative).to_string());
                 sol_log(&format!("Miner deployed: {:?}", miner.deployed).to_string());
-                if top_miner_sample >= miner.cumulative[winning_square]
-                    && top_miner_sample
-                        < miner.cumulative[winning_square] + miner.deployed[winning_square]
-                {
+                sol_log(&format!("Miner mass: {:?}", miner.mass).to_string());
+                if miner.is_top_miner(round, top_miner_sample, winning_square) {
                     sol_log("Top miner verified");
                     round.top_miner = miner.authority;
                 } else {
-                    panic!("Top miner verification failed");
+                    return Err(OreError::TopMinerVerificationFailed.into());
                 }
             } else {
-                panic!("Top miner round id mismatch");
+                return Err(OreError::TopMinerRoundMismatch.into());
             }
         } else {
-            panic!("Top miner account cannot be parsed");
+            return Err(OreError::InvalidTopMiner.into());
         }
     } else {
         sol_log("Split round");
@@ -1,28 +1,51 @@
 use ore_api::prelude::*;
+use ore_api::sdk::program_log;
 use steel::*;
 
-/// Sets the admin.
-pub fn process_set_admin(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+use crate::migrate_config;
+
+/// Proposes a new admin. The proposed admin must accept before the handover takes effect.
+pub fn process_set_admin(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
     // Parse data.
-    // let args = SetAdmin::try_from_bytes(data)?;
-    // let new_admin = Pubkey::new_from_array(args.admin);
+    let args = SetAdmin::try_from_bytes(data)?;
+    let new_admin = Pubkey::new_from_array(args.admin);
 
     // Load accounts.
-    let [signer_info, config_info, system_program] = accounts else {
+    let clock = Clock::get()?;
+    let [signer_info, board_info, config_info, system_program, ore_program] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
     signer_info.is_signer()?;
+    board_info.has_address(&BOARD_ADDRESS)?;
+    config_info
+        .is_writable()?
+        .has_seeds(&[CONFIG], &ore_api::ID)?;
+    system_program.is_program(&system_program::ID)?;
+    ore_program.is_program(&ore_api::ID)?;
+
+    // Migrate config.
+    migrate_config(config_info, signer_info)?;
     let config = config_info
-        .has_seeds(&[CONFIG], &ore_api::ID)?
         .as_account_mut::<Config>(&ore_api::ID)?
         .assert_mut_err(
             |c| c.admin.authority == *signer_info.key,
             OreError::NotAuthorized.into(),
         )?;
-    system_program.is_program(&system_program::ID)?;
 
-    // Set admin.
-    // config.admin.authority = new_admin;
+    // Propose admin.
+    config.pending_admin = new_admin;
+
+    // Emit event.
+    program_log(
+        &[board_info.clone(), ore_program.clone()],
+        SetAdminEvent {
+            disc: 5,
+            authority: config.admin.authority,
+            pending_admin: new_admin,
+            ts: clock.unix_timestamp,
+        }
+        .to_bytes(),
+    )?;
 
     Ok(())
 }
@@ -0,0 +1,34 @@
+use ore_api::prelude::*;
+use steel::*;
+
+use crate::migrate_config;
+
+/// Sets the admin fee rate charged on all squares. Takes effect from the next round.
+pub fn process_set_admin_fee(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = SetAdminFee::try_from_bytes(data)?;
+    let admin_fee = u64::from_le_bytes(args.admin_fee);
+
+    // Load accounts.
+    let [signer_info, config_info, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    config_info.is_writable()?.has_address(&CONFIG_ADDRESS)?;
+    system_program.is_program(&system_program::ID)?;
+    migrate_config(config_info, signer_info)?;
+    let config = config_info
+        .as_account_mut::<Config>(&ore_api::ID)?
+        .assert_mut_err(
+            |c| c.admin.authority == *signer_info.key,
+            OreError::NotAuthorized.into(),
+        )?;
+
+    // Validate fee rate. Zero is reserved for the default rate.
+    Config::validate_admin_fee_rate(admin_fee)?;
+
+    // Set fee rate.
+    config.admin.fee_rate = admin_fee;
+
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use ore_api::prelude::*;
+use steel::*;
+
+use crate::migrate_config;
+
+/// Sets the address which collects the admin fee.
+pub fn process_set_fee_collector(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = SetFeeCollector::try_from_bytes(data)?;
+    let fee_collector = Pubkey::new_from_array(args.fee_collector);
+
+    // Load accounts.
+    let [signer_info, config_info, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    config_info.is_writable()?.has_address(&CONFIG_ADDRESS)?;
+    system_program.is_program(&system_program::ID)?;
+    migrate_config(config_info, signer_info)?;
+    let config = config_info
+        .as_account_mut::<Config>(&ore_api::ID)?
+        .assert_mut_err(
+            |c| c.admin.authority == *signer_info.key,
+            OreError::NotAuthorized.into(),
+        )?;
+
+    // Validate fee collector.
+    if fee_collector == Pubkey::default() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    // Set fee collector.
+    config.admin.fee_collector = fee_collector;
+
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use ore_api::prelude::*;
+use steel::*;
+
+use crate::migrate_config;
+
+/// Sets the protocol fee rate charged on losing squares. Takes effect from the next round.
+pub fn process_set_fee_rate(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = SetFeeRate::try_from_bytes(data)?;
+    let fee_rate = u64::from_le_bytes(args.fee_rate);
+
+    // Load accounts.
+    let [signer_info, config_info, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    config_info.is_writable()?.has_address(&CONFIG_ADDRESS)?;
+    system_program.is_program(&system_program::ID)?;
+    migrate_config(config_info, signer_info)?;
+    let config = config_info
+        .as_account_mut::<Config>(&ore_api::ID)?
+        .assert_mut_err(
+            |c| c.protocol.authority == *signer_info.key,
+            OreError::NotAuthorized.into(),
+        )?;
+
+    // Validate fee rate. Zero is reserved for the default rate.
+    Config::validate_protocol_fee_rate(fee_rate)?;
+
+    // Set fee rate.
+    config.protocol.fee_rate = fee_rate;
+
+    Ok(())
+}
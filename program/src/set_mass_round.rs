@@ -0,0 +1,39 @@
+use ore_api::prelude::*;
+use steel::*;
+
+use crate::migrate_config;
+
+/// Sets the first round in which ORE rewards are weighted by mass. Zero disables mass-weighted rewards.
+pub fn process_set_mass_round(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = SetMassRound::try_from_bytes(data)?;
+    let mass_round_id = u64::from_le_bytes(args.mass_round_id);
+
+    // Load accounts.
+    let [signer_info, board_info, config_info, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let board = board_info
+        .has_address(&BOARD_ADDRESS)?
+        .as_account::<Board>(&ore_api::ID)?;
+    config_info.is_writable()?.has_address(&CONFIG_ADDRESS)?;
+    system_program.is_program(&system_program::ID)?;
+    migrate_config(config_info, signer_info)?;
+    let config = config_info
+        .as_account_mut::<Config>(&ore_api::ID)?
+        .assert_mut_err(
+            |c| c.protocol.authority == *signer_info.key,
+            OreError::NotAuthorized.into(),
+        )?;
+
+    // Validate round. The current round has already been opened, so activation must be in a future round.
+    if mass_round_id != 0 && mass_round_id <= board.round_id {
+        return Err(OreError::InvalidMassRound.into());
+    }
+
+    // Set mass round.
+    config.mass_round_id = mass_round_id;
+
+    Ok(())
+}
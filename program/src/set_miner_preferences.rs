@@ -0,0 +1,60 @@
+use ore_api::prelude::*;
+use solana_program::log::sol_log;
+use steel::*;
+
+use crate::migrate_miner;
+
+/// Updates a miner's account preferences.
+pub fn process_set_miner_preferences(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    // Legacy payloads only update auto return, and leave the claim delegate as is.
+    let (auto_return, claim_delegate) =
+        if let Ok(args) = SetMinerPreferencesV2::try_from_bytes(data) {
+            let claim_delegate = (
+                Pubkey::new_from_array(args.claim_delegate),
+                Pubkey::new_from_array(args.claim_destination),
+            );
+            (args.auto_return > 0, Some(claim_delegate))
+        } else {
+            let args = SetMinerPreferences::try_from_bytes(data)?;
+            (args.auto_return > 0, None)
+        };
+
+    // A claim delegate must have a destination to send rewards to.
+    if let Some((claim_delegate, claim_destination)) = claim_delegate {
+        if claim_delegate != Pubkey::default() && claim_destination == Pubkey::default() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+    }
+
+    // Load accounts.
+    let [signer_info, miner_info, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    miner_info
+        .is_writable()?
+        .has_seeds(&[MINER, &signer_info.key.to_bytes()], &ore_api::ID)?;
+    migrate_miner(miner_info, signer_info)?;
+    let miner = miner_info
+        .as_account_mut::<Miner>(&ore_api::ID)?
+        .assert_mut_err(
+            |m| m.authority == *signer_info.key,
+            OreError::NotAuthorized.into(),
+        )?;
+    system_program.is_program(&system_program::ID)?;
+
+    // Update preferences.
+    miner.auto_return = auto_return as u64;
+    sol_log(&format!("Auto return: {}", auto_return));
+    if let Some((claim_delegate, claim_destination)) = claim_delegate {
+        miner.claim_delegate = claim_delegate;
+        miner.claim_destination = claim_destination;
+        sol_log(&format!(
+            "Claim delegate: {} -> {}",
+            claim_delegate, claim_destination
+        ));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use ore_api::prelude::*;
+use steel::*;
+
+use crate::migrate_config;
+
+/// Sets the round and intermission lengths. Takes effect from the next round.
+pub fn process_set_round_timing(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = SetRoundTiming::try_from_bytes(data)?;
+    let round_slots = u64::from_le_bytes(args.round_slots);
+    let intermission_slots = u64::from_le_bytes(args.intermission_slots);
+
+    // Load accounts.
+    let [signer_info, config_info, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    config_info.is_writable()?.has_address(&CONFIG_ADDRESS)?;
+    system_program.is_program(&system_program::ID)?;
+    migrate_config(config_info, signer_info)?;
+    let config = config_info
+        .as_account_mut::<Config>(&ore_api::ID)?
+        .assert_mut_err(
+            |c| c.protocol.authority == *signer_info.key,
+            OreError::NotAuthorized.into(),
+        )?;
+
+    // Validate timing.
+    if !(MIN_ROUND_SLOTS..=MAX_ROUND_SLOTS).contains(&round_slots)
+        || !(MIN_INTERMISSION_SLOTS..=MAX_INTERMISSION_SLOTS).contains(&intermission_slots)
+    {
+        return Err(OreError::InvalidRoundTiming.into());
+    }
+
+    // Set timing.
+    config.protocol.round_slots = round_slots;
+    config.protocol.intermission_slots = intermission_slots;
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use entropy_api::state::Var;
+use ore_api::prelude::*;
+use steel::*;
+
+use crate::migrate_config;
+
+/// Switches the entropy var used to sample rounds. Only allowed between rounds.
+pub fn process_set_var(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let [signer_info, board_info, config_info, var_info, system_program, entropy_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    board_info
+        .has_address(&BOARD_ADDRESS)?
+        .as_account::<Board>(&ore_api::ID)?
+        .assert_err(|b| b.end_slot == u64::MAX, OreError::RoundInProgress.into())?;
+    config_info.is_writable()?.has_address(&CONFIG_ADDRESS)?;
+    system_program.is_program(&system_program::ID)?;
+    entropy_program.is_program(&entropy_api::ID)?;
+    var_info
+        .as_account::<Var>(&entropy_api::ID)?
+        .assert(|v| v.authority == *board_info.key)?;
+    migrate_config(config_info, signer_info)?;
+    let config = config_info
+        .as_account_mut::<Config>(&ore_api::ID)?
+        .assert_mut_err(
+            |c| c.protocol.authority == *signer_info.key,
+            OreError::NotAuthorized.into(),
+        )?;
+
+    // Set entropy var.
+    config.protocol.entropy_var_address = *var_info.key;
+    config.protocol.entropy_program_id = *entropy_program.key;
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use ore_api::prelude::*;
+use steel::*;
+
+use crate::migrate_config;
+
+/// Sets the withdrawal cutoff and penalty.
+pub fn process_set_withdraw_config(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = SetWithdrawConfig::try_from_bytes(data)?;
+    let cutoff_slots = u64::from_le_bytes(args.cutoff_slots);
+    let penalty_rate = u64::from_le_bytes(args.penalty_rate);
+
+    // Load accounts.
+    let [signer_info, config_info, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    config_info.is_writable()?.has_address(&CONFIG_ADDRESS)?;
+    system_program.is_program(&system_program::ID)?;
+    migrate_config(config_info, signer_info)?;
+    let config = config_info
+        .as_account_mut::<Config>(&ore_api::ID)?
+        .assert_mut_err(
+            |c| c.protocol.authority == *signer_info.key,
+            OreError::NotAuthorized.into(),
+        )?;
+
+    // Validate config.
+    if cutoff_slots > MAX_ROUND_SLOTS {
+        return Err(OreError::InvalidRoundTiming.into());
+    }
+    if penalty_rate > DENOMINATOR_BPS {
+        return Err(OreError::FeeRateTooHigh.into());
+    }
+
+    // Set withdraw config.
+    config.withdraw_cutoff_slots = cutoff_slots;
+    config.withdraw_penalty_rate = penalty_rate;
+
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use ore_api::prelude::*;
+use solana_program::{log::sol_log, native_token::lamports_to_sol};
+use steel::*;
+
+use crate::{migrate_automation, migrate_config, migrate_miner, migrate_round};
+
+/// Withdraws SOL deployed on a square before the round ends.
+/// Only the miner who deployed last on a square may withdraw from it.
+pub fn process_withdraw(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = Withdraw::try_from_bytes(data)?;
+    let square = u64::from_le_bytes(args.square) as usize;
+    let amount = u64::from_le_bytes(args.amount);
+    if square >= 25 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    // Load accounts.
+    let clock = Clock::get()?;
+    let [signer_info, automation_info, board_info, config_info, miner_info, round_info, treasury_info, system_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let board = board_info
+        .has_address(&BOARD_ADDRESS)?
+        .as_account::<Board>(&ore_api::ID)?;
+    config_info.is_writable()?.has_address(&CONFIG_ADDRESS)?;
+    migrate_config(config_info, signer_info)?;
+    let config = config_info.as_account::<Config>(&ore_api::ID)?;
+    round_info.has_seeds(&[ROUND, &board.round_id.to_le_bytes()], &ore_api::ID)?;
+    migrate_round(round_info, signer_info)?;
+    let round = round_info
+        .as_account_mut::<Round>(&ore_api::ID)?
+        .assert_mut(|r| r.id == board.round_id)?;
+    miner_info.has_seeds(&[MINER, &signer_info.key.to_bytes()], &ore_api::ID)?;
+    migrate_miner(miner_info, signer_info)?;
+    let miner = miner_info
+        .as_account_mut::<Miner>(&ore_api::ID)?
+        .assert_mut_err(
+            |m| m.authority == *signer_info.key,
+            OreError::NotAuthorized.into(),
+        )?
+        .assert_mut_err(|m| m.round_id == round.id, OreError::InvalidWithdraw.into())?;
+    automation_info
+        .is_writable()?
+        .has_address(&miner.automation_address())?;
+    treasury_info
+        .is_writable()?
+        .has_address(&TREASURY_ADDRESS)?;
+    system_program.is_program(&system_program::ID)?;
+
+    // Withdrawals are only open until the cutoff before the end of the round.
+    if config.withdraw_cutoff_slots == 0
+        || clock.slot < board.start_slot
+        || clock.slot >= board.end_slot.saturating_sub(config.withdraw_cutoff_slots)
+    {
+        return Err(OreError::WithdrawClosed.into());
+    }
+
+    // Normalize amount.
+    let amount = amount.min(miner.deployed[square]);
+    if amount == 0 {
+        return Err(OreError::AmountTooSmall.into());
+    }
+
+    // Release the miner's range, which must be at the end of the square so other ranges are untouched.
+    let Some(mass) = miner.release_range(round, square, amount) else {
+        return Err(OreError::InvalidWithdraw.into());
+    };
+
+    // Update miner.
+    miner.deployed[square] -= amount;
+    miner.mass[square] -= mass;
+    miner.lifetime_deployed = miner.lifetime_deployed.saturating_sub(amount);
+
+    // Update round.
+    round.deployed[square] -= amount;
+    round.mass[square] -= mass;
+    if miner.deployed[square] == 0 {
+        round.count[square] -= 1;
+    }
+    let is_round_withdrawn = miner.deployed.iter().sum::<u64>() == 0;
+    if is_round_withdrawn {
+        round.total_miners -= 1;
+    }
+
+    // Transfer SOL, charging the penalty to the treasury.
+    let penalty =
+        (amount as u128 * config.withdraw_penalty_rate as u128 / DENOMINATOR_BPS as u128) as u64;
+    round_info.send(penalty, treasury_info);
+
+    // Return SOL to the automation if it funded the round, otherwise to the miner authority.
+    migrate_automation(automation_info, signer_info)?;
+    let automation = if !automation_info.data_is_empty() && !miner.is_manually_funded() {
+        let automation = automation_info.as_account_mut::<Automation>(&ore_api::ID)?;
+        (automation.last_round_id == round.id).then_some(automation)
+    } else {
+        None
+    };
+    if let Some(automation) = automation {
+        automation.record_withdraw(
+            amount,
+            amount - penalty,
+            is_round_withdrawn,
+            clock.unix_timestamp,
+        );
+        round_info.send(amount - penalty, automation_info);
+    } else {
+        round_info.send(amount - penalty, signer_info);
+    }
+
+    sol_log(&format!(
+        "Round #{}: withdrawing {} SOL from square {} ({} SOL penalty)",
+        round.id,
+        lamports_to_sol(amount),
+        square,
+        lamports_to_sol(penalty),
+    ));
+
+    Ok(())
+}
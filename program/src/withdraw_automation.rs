@@ -0,0 +1,62 @@
+use ore_api::prelude::*;
+use solana_program::{log::sol_log, native_token::lamports_to_sol};
+use steel::*;
+
+use crate::{log_automation, migrate_automation};
+
+/// Withdraws SOL from an automation balance, without closing the automation.
+pub fn process_withdraw_automation(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = WithdrawAutomation::try_from_bytes(data)?;
+    let amount = u64::from_le_bytes(args.amount);
+
+    // Load accounts.
+    let clock = Clock::get()?;
+    let [signer_info, automation_info, board_info, system_program, ore_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    migrate_automation(automation_info.is_writable()?, signer_info)?;
+    let automation = automation_info
+        .as_account_mut::<Automation>(&ore_api::ID)?
+        .assert_mut_err(
+            |a| a.authority == *signer_info.key,
+            OreError::NotAuthorized.into(),
+        )?;
+    automation_info.has_address(&automation.pda().0)?;
+    board_info.has_address(&BOARD_ADDRESS)?;
+    system_program.is_program(&system_program::ID)?;
+    ore_program.is_program(&ore_api::ID)?;
+
+    // Normalize amount.
+    let amount = amount.min(automation.balance);
+    if amount == 0 {
+        return Err(OreError::AmountTooSmall.into());
+    }
+
+    // The automation must keep enough balance to deploy a full round.
+    // To withdraw everything, the automation must be closed instead.
+    if automation.balance - amount < automation.min_round_balance() {
+        return Err(OreError::AutomationBalanceTooLow.into());
+    }
+
+    // Transfer SOL to the authority.
+    automation.balance -= amount;
+    automation_info.send(amount, signer_info);
+
+    sol_log(&format!(
+        "Withdrawing {} SOL from automation",
+        lamports_to_sol(amount)
+    ));
+
+    // Emit event.
+    log_automation(
+        &[board_info.clone(), ore_program.clone()],
+        automation,
+        AutomationEventType::Withdrawn,
+        amount,
+        clock.unix_timestamp,
+    )?;
+
+    Ok(())
+}
@@ -29,10 +29,9 @@ pub fn process_wrap(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult
 
     // Check min balance.
     let min_balance = Rent::get()?.minimum_balance(Treasury::SIZE);
-    assert!(
-        treasury_info.lamports() >= min_balance,
-        "Insufficient SOL balance"
-    );
+    if treasury_info.lamports() < min_balance {
+        return Err(OreError::InsufficientTreasuryBalance.into());
+    }
 
     Ok(())
 }